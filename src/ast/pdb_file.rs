@@ -1,5 +1,31 @@
 use super::types::*;
-use std::iter::Iterator;
+use crate::{
+    compnd::validate_tax_ids, error::ProteinError, header::validate_date, helix::check_length,
+    model::missing_model_serials, residue::is_water, seqres::check_serial_numbers,
+    validate::in_title_section,
+};
+use itertools::Either;
+use std::{
+    collections::{BTreeMap, HashSet},
+    iter::Iterator,
+};
+
+/// Warnings for tax ids and unknown keys among `tokens` of a COMPND or SOURCE record
+fn token_warnings(record: &'static str, tokens: &[Token]) -> Vec<ParseWarning> {
+    tokens
+        .iter()
+        .flat_map(|t| match t {
+            Token::OrganismTaxId { id } | Token::ExpressionSystemTaxId { id } => {
+                validate_tax_ids(id)
+            }
+            Token::Unknown { key, .. } => vec![ParseWarning::UnknownToken {
+                record,
+                key: key.clone(),
+            }],
+            _ => Vec::new(),
+        })
+        .collect()
+}
 
 macro_rules! impl_record_filter {
     ($fn_name : ident -> $match_type: ident -> $ret_type :ident ) => {
@@ -18,8 +44,33 @@ macro_rules! impl_record_filter {
     };
 }
 
+macro_rules! impl_record_require {
+    ($fn_name : ident -> $match_type: ident -> $ret_type :ident, $record_name : expr) => {
+        #[doc = concat!("First ", $record_name, " record, an error if the entry has none")]
+        pub fn $fn_name(&self) -> Result<&$ret_type, ProteinError> {
+            self.records
+                .iter()
+                .find_map(|r| match r {
+                    Record::$match_type(a) => Some(a),
+                    _ => None,
+                })
+                .ok_or(ProteinError::MissingRecord($record_name))
+        }
+    };
+}
+
+#[derive(Debug, Clone)]
 pub struct PdbFile<I> {
     records: I,
+    raw: Option<Vec<RawLines>>,
+}
+
+/// Result of the lenient [parse_with_options](../../fn.parse_with_options.html) entry point.
+/// Non fatal issues found in the parsed records are collected in `warnings`
+#[derive(Debug, Clone)]
+pub struct ParseResult {
+    pub pdb: PdbFile<Vec<Record>>,
+    pub warnings: Vec<ParseWarning>,
 }
 
 pub trait ToPdbFile {
@@ -30,11 +81,168 @@ pub trait ToPdbFile {
 impl ToPdbFile for Vec<Record> {
     type I = Vec<Record>;
     fn to_pdb_file(self) -> PdbFile<Self::I> {
-        PdbFile { records: self }
+        PdbFile {
+            records: self,
+            raw: None,
+        }
     }
 }
 
 impl PdbFile<Vec<Record>> {
+    /// creates a file whose records carry the source lines they were parsed from, `raw`
+    /// holding the lines of each record in `records`
+    pub(crate) fn with_raw(records: Vec<Record>, raw: Vec<RawLines>) -> Self {
+        PdbFile {
+            records,
+            raw: Some(raw),
+        }
+    }
+
+    /// Records in the order they appear in the file
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    /// Iterates records along with the lines of the original input each was parsed from,
+    /// before any rewriting pre-pass. Lines are only available when parsed with `keep_raw`
+    /// option set.
+    pub fn records_with_raw(&self) -> impl Iterator<Item = (&Record, Option<&RawLines>)> {
+        self.records
+            .iter()
+            .enumerate()
+            .map(move |(i, r)| (r, self.raw.as_ref().and_then(|raw| raw.get(i))))
+    }
+
+    /// Collects non fatal issues of parsed records, such as SEQRES residue counts
+    /// disagreeing with the residues listed or implausible taxonomy ids
+    pub fn warnings(&self) -> Vec<ParseWarning> {
+        self.records
+            .iter()
+            .flat_map(|r| match r {
+                Record::Seqres(s) => {
+                    let mut warnings = check_serial_numbers(s);
+                    if s.residues.len() != s.num_res as usize {
+                        warnings.push(ParseWarning::CountMismatch {
+                            record: "SEQRES",
+                            expected: s.num_res as usize,
+                            found: s.residues.len(),
+                        });
+                    }
+                    warnings
+                }
+                Record::Helix(h) => check_length(h).into_iter().collect(),
+                Record::Cmpnd(Cmpnd { tokens }) => token_warnings("COMPND", tokens),
+                Record::Source(Source { tokens }) => token_warnings("SOURCE", tokens),
+                Record::Header(h) => validate_date("HEADER", h.deposition_date)
+                    .into_iter()
+                    .collect(),
+                Record::Obslte(o) => validate_date("OBSLTE", o.replacement_date)
+                    .into_iter()
+                    .collect(),
+                Record::Sprsde(s) => validate_date("SPRSDE", s.sprsde_date).into_iter().collect(),
+                Record::Revdats(r) => r
+                    .revdat
+                    .iter()
+                    .filter_map(|r| validate_date("REVDAT", r.modification_date))
+                    .collect(),
+                _ => Vec::new(),
+            })
+            .chain(
+                missing_model_serials(self.model_serials())
+                    .into_iter()
+                    .map(|missing| ParseWarning::ModelGap { missing }),
+            )
+            .collect()
+    }
+
+    impl_record_require!(require_header -> Header -> Header, "HEADER");
+    impl_record_require!(require_title -> Title -> Title, "TITLE");
+    impl_record_require!(require_cmpnd -> Cmpnd -> Cmpnd, "COMPND");
+    impl_record_require!(require_source -> Source -> Source, "SOURCE");
+    impl_record_require!(require_keywds -> Keywds -> Keywds, "KEYWDS");
+    impl_record_require!(require_expdta -> Experimental -> Experimental, "EXPDTA");
+    impl_record_require!(require_authors -> Authors -> Authors, "AUTHOR");
+
+    /// Description of `site` given in REMARK 800, matched by site id
+    pub fn site_description(&self, site: &Site) -> Option<&SiteDescription> {
+        self.records
+            .iter()
+            .filter_map(|r| match r {
+                Record::SiteDescriptions(d) => Some(d),
+                _ => None,
+            })
+            .flatten()
+            .find(|d| d.id == site.id)
+    }
+
+    /// Title of the entry with continuation lines joined, `None` without a TITLE record
+    pub fn title(&self) -> Option<&str> {
+        self.records.iter().find_map(|r| match r {
+            Record::Title(t) => Some(t.title.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Resolution in Angstroms given in REMARK 2. `None` when not applicable and for
+    /// theoretical models, which are not experimental structures
+    pub fn resolution(&self) -> Option<f64> {
+        let experimental = self.records.iter().all(|r| match r {
+            Record::Experimental(e) => e.is_experimental(),
+            _ => true,
+        });
+        self.records
+            .iter()
+            .find_map(|r| match r {
+                Record::Resolution(r) => r.angstroms,
+                _ => None,
+            })
+            .filter(|_| experimental)
+    }
+
+    /// Distinct heterogen residues other than water, in the order they are listed in HET
+    /// records followed by those only found in HETATM records of the first model
+    pub fn ligands(&self) -> Vec<ResidueId> {
+        let het = self.records.iter().filter_map(|r| match r {
+            Record::Het(h) if !is_water(&h.het_id) => Some(ResidueId {
+                chain_id: h.chain_id,
+                residue_seq: h.residue_seq,
+                insertion_code: h.insertion_code,
+            }),
+            _ => None,
+        });
+        let hetatm = self
+            .first_model_atoms()
+            .filter(|a| a.hetero && !is_water(&a.residue_name))
+            .map(|a| a.residue_id());
+        let mut ligands: Vec<ResidueId> = Vec::new();
+        for id in het.chain(hetatm) {
+            if !ligands.contains(&id) {
+                ligands.push(id);
+            }
+        }
+        ligands
+    }
+
+    /// Distinct het identifiers of HET and HETNAM records in the order they are listed,
+    /// along with their chemical name. Heterogens without a HETNAM record are named by the
+    /// description of their HET record, if any
+    pub fn heterogen_types(&self) -> Vec<(String, String)> {
+        let mut types: Vec<(String, String)> = Vec::new();
+        for r in self.records.iter() {
+            let (het_id, name, named) = match r {
+                Record::Het(h) => (&h.het_id, h.text.as_deref().unwrap_or_default(), false),
+                Record::HetName(h) => (&h.het_id, h.name.as_str(), true),
+                _ => continue,
+            };
+            match types.iter_mut().find(|(id, _)| id == het_id) {
+                Some((_, known)) if named => *known = name.to_owned(),
+                Some(_) => {}
+                None => types.push((het_id.clone(), name.to_owned())),
+            }
+        }
+        types
+    }
+
     pub fn header(&mut self) -> PdbHeader<&mut Vec<Record>> {
         PdbHeader {
             records: &mut self.records,
@@ -46,6 +254,214 @@ impl PdbFile<Vec<Record>> {
             records: &mut self.records,
         }
     }
+
+    /// Returns models of the coordinate section. When the entry has no MODEL records
+    /// its ATOM and HETATM records form a single implicit model numbered 1
+    pub fn models(&self) -> Vec<Model> {
+        let models = self
+            .records
+            .iter()
+            .filter_map(|r| match r {
+                Record::Model(m) => Some(m.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if !models.is_empty() {
+            return models;
+        }
+        let atoms = self
+            .records
+            .iter()
+            .filter_map(|r| match r {
+                Record::Atom(a) => Some(a.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if atoms.is_empty() {
+            Vec::new()
+        } else {
+            vec![Model { serial: 1, atoms }]
+        }
+    }
+
+    /// Serials of the models of the coordinate section as [models](#method.models) numbers
+    /// them, without cloning their atoms
+    pub fn model_serials(&self) -> impl Iterator<Item = u32> + '_ {
+        let implicit = !self.records.iter().any(|r| matches!(r, Record::Model(_)))
+            && self.records.iter().any(|r| matches!(r, Record::Atom(_)));
+        self.records
+            .iter()
+            .filter_map(|r| match r {
+                Record::Model(m) => Some(m.serial),
+                _ => None,
+            })
+            .chain(std::iter::once(1).filter(move |_| implicit))
+    }
+
+    /// Atoms of the first model as [models](#method.models) returns it, borrowed from the
+    /// records. These are the atoms counted by the count accessors
+    pub fn first_model_atoms(&self) -> impl Iterator<Item = &Atom> {
+        match self.records.iter().find_map(|r| match r {
+            Record::Model(m) => Some(m),
+            _ => None,
+        }) {
+            Some(model) => Either::Left(model.atoms.iter()),
+            None => Either::Right(self.records.iter().filter_map(|r| match r {
+                Record::Atom(a) => Some(a),
+                _ => None,
+            })),
+        }
+    }
+
+    /// Number of ATOM and HETATM records of the first model
+    pub fn atom_count(&self) -> usize {
+        self.first_model_atoms().count()
+    }
+
+    /// Number of distinct residues of the first model, waters and ligands included
+    pub fn residue_count(&self) -> usize {
+        self.first_model_atoms()
+            .map(Atom::residue_id)
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// Number of distinct chain identifiers of the first model
+    pub fn chain_count(&self) -> usize {
+        self.first_model_atoms()
+            .map(|a| a.chain_id)
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// Rotates every atom of the file by `m` and then moves it by `t`
+    pub fn transform_all(&mut self, m: &[[f64; 3]; 3], t: [f64; 3]) {
+        let atoms = self.records.iter_mut().flat_map(|r| match r {
+            Record::Atom(a) => std::slice::from_mut(a),
+            Record::Model(model) => model.atoms.as_mut_slice(),
+            _ => &mut [],
+        });
+        for a in atoms {
+            a.apply_matrix(m);
+            a.translate(t[0], t[1], t[2]);
+        }
+    }
+}
+
+/// values of `tokens` keyed by the MOL_ID they follow and their key
+fn token_values(tokens: &[Token]) -> BTreeMap<(Option<u32>, &str), String> {
+    let mut mol_id = None;
+    let mut values = BTreeMap::new();
+    for token in tokens {
+        match token {
+            Token::MoleculeId(id) => mol_id = Some(*id),
+            t => {
+                values.insert((mol_id, t.key()), t.value_string());
+            }
+        }
+    }
+    values
+}
+
+/// items of the HEADER, TITLE, COMPND and SOURCE records of `pdb`, keyed by record,
+/// MOL_ID and field
+fn header_values(
+    pdb: &PdbFile<Vec<Record>>,
+) -> BTreeMap<(&'static str, Option<u32>, &str), String> {
+    let mut values = BTreeMap::new();
+    for record in pdb.records() {
+        match record {
+            Record::Header(h) => {
+                values.insert(("HEADER", None, "classification"), h.classification.clone());
+            }
+            Record::Title(t) => {
+                values.insert(("TITLE", None, "title"), t.title.clone());
+            }
+            Record::Cmpnd(c) => values.extend(
+                token_values(&c.tokens)
+                    .into_iter()
+                    .map(|((mol_id, key), v)| (("COMPND", mol_id, key), v)),
+            ),
+            Record::Source(s) => values.extend(
+                token_values(&s.tokens)
+                    .into_iter()
+                    .map(|((mol_id, key), v)| (("SOURCE", mol_id, key), v)),
+            ),
+            _ => (),
+        }
+    }
+    values
+}
+
+/// Compares classification, title and COMPND and SOURCE tokens of two entries. Tokens
+/// are matched by the MOL_ID of their molecule and their key. Differences are returned
+/// ordered by record, MOL_ID and key.
+pub fn diff_headers(a: &PdbFile<Vec<Record>>, b: &PdbFile<Vec<Record>>) -> Vec<HeaderDiff> {
+    let mut from = header_values(a);
+    let to = header_values(b);
+    let mut diffs = Vec::new();
+    for ((record, mol_id, key), to_value) in to {
+        let change = match from.remove(&(record, mol_id, key)) {
+            None => HeaderChange::Added(to_value),
+            Some(from_value) if from_value != to_value => HeaderChange::Changed {
+                from: from_value,
+                to: to_value,
+            },
+            Some(_) => continue,
+        };
+        diffs.push(HeaderDiff {
+            record,
+            mol_id,
+            key: key.to_owned(),
+            change,
+        });
+    }
+    diffs.extend(
+        from.into_iter()
+            .map(|((record, mol_id, key), value)| HeaderDiff {
+                record,
+                mol_id,
+                key: key.to_owned(),
+                change: HeaderChange::Removed(value),
+            }),
+    );
+    diffs.sort_by(|x, y| (x.record, x.mol_id, &x.key).cmp(&(y.record, y.mol_id, &y.key)));
+    diffs
+}
+
+/// Merges entries which together form a single large structure, as listed in their
+/// SPLIT records. Records of the entries are concatenated in the given order, except for
+/// the title section records such as HEADER and TITLE, which are only kept from the first
+/// entry. Models are renumbered from 1 in the merged order.
+pub fn merge_split(entries: Vec<PdbFile<Vec<Record>>>) -> PdbFile<Vec<Record>> {
+    let mut serials = 1..;
+    entries
+        .into_iter()
+        .enumerate()
+        .flat_map(|(i, e)| {
+            e.records
+                .into_iter()
+                .filter(move |r| i == 0 || !in_title_section(r))
+        })
+        .map(|r| match r {
+            Record::Model(m) => Record::Model(Model {
+                serial: serials.next().unwrap_or_default(),
+                ..m
+            }),
+            r => r,
+        })
+        .collect::<Vec<_>>()
+        .to_pdb_file()
+}
+
+/// Iterates records of the given kind, in file order
+pub fn filter_records(records: &[Record], kind: RecordKind) -> impl Iterator<Item = &Record> {
+    records.iter().filter(move |r| r.kind() == kind)
+}
+
+/// First record of the given kind
+pub fn find_first(records: &[Record], kind: RecordKind) -> Option<&Record> {
+    records.iter().find(|r| r.kind() == kind)
 }
 
 pub struct PdbHeader<I> {
@@ -77,7 +493,7 @@ pub struct PdbJournal<I> {
     records: I,
 }
 
-impl<'a> PdbJournal<&'a mut Vec<Record>> {
+impl PdbJournal<&mut Vec<Record>> {
     impl_record_filter!(authors -> JournalAuthors -> JournalAuthors);
     impl_record_filter!(title -> JournalTitle -> JournalTitle);
     impl_record_filter!(editors -> JournalEditors -> JournalEditors);
@@ -86,13 +502,18 @@ impl<'a> PdbJournal<&'a mut Vec<Record>> {
     impl_record_filter!(publication -> JournalPublication -> JournalPublication);
     impl_record_filter!(pubmedid -> JournalPubMedId -> JournalPubMedId);
     impl_record_filter!(doi -> JournalDoi -> JournalDoi);
+
+    /// PubMed id of the primary citation, `None` if the entry has no PMID sub-record
+    pub fn pmid(&mut self) -> Option<u32> {
+        self.pubmedid().map(|p| p.id)
+    }
 }
 
 pub struct PrimaryStructure<I> {
     records: I,
 }
 
-impl<'a> PrimaryStructure<&'a mut Vec<Record>> {
+impl PrimaryStructure<&mut Vec<Record>> {
     impl_record_filter!(dbreference -> Dbref -> Dbref);
     impl_record_filter!(conflicts -> Seqadv -> Seqadv);
     impl_record_filter!(residues -> Seqres -> Seqres);
@@ -113,4 +534,229 @@ mod test {
         assert_eq!(tit.unwrap().title, "a".to_owned());
         assert_eq!(parsed_pdb.header().nummdl().unwrap().num, 1);
     }
+
+    #[test]
+    fn test_require_header() {
+        use super::{super::types::Record, *};
+        let pdb = vec![Record::Title(Title {
+            title: "a".to_owned(),
+        })]
+        .to_pdb_file();
+        assert_eq!(pdb.require_title().unwrap().title, "a");
+        match pdb.require_header() {
+            Err(e @ ProteinError::MissingRecord("HEADER")) => {
+                assert_eq!(e.to_string(), "Mandatory HEADER record is missing")
+            }
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_journal_pmid() {
+        use super::{super::types::Record, *};
+        let mut pdb = vec![Record::JournalPubMedId(JournalPubMedId { id: 12345678 })].to_pdb_file();
+        assert_eq!(pdb.header().journal().pmid(), Some(12345678));
+        let mut pdb = vec![Record::JournalDoi(JournalDoi {
+            id: "10.1000/182".to_owned(),
+        })]
+        .to_pdb_file();
+        assert_eq!(pdb.header().journal().pmid(), None);
+    }
+
+    #[test]
+    fn test_filter_records() {
+        use super::{super::types::Record, *};
+        let records = vec![
+            Record::Header(Header {
+                classification: "HYDROLASE".to_owned(),
+                ..Default::default()
+            }),
+            Record::Atom(Atom {
+                serial: 1,
+                ..Default::default()
+            }),
+            Record::Remark("   2".to_owned()),
+            Record::Atom(Atom {
+                serial: 2,
+                ..Default::default()
+            }),
+        ];
+        assert_eq!(filter_records(&records, RecordKind::Atom).count(), 2);
+        match find_first(&records, RecordKind::Header) {
+            Some(Record::Header(h)) => assert_eq!(h.classification, "HYDROLASE"),
+            r => panic!("{:?}", r),
+        }
+        assert!(find_first(&records, RecordKind::Model).is_none());
+    }
+
+    #[test]
+    fn test_transform_all() {
+        use super::{super::types::Record, *};
+        let mut pdb = vec![Record::Atom(Atom {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            ..Default::default()
+        })]
+        .to_pdb_file();
+        pdb.transform_all(
+            &[[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            [1.0, 1.0, 1.0],
+        );
+        if let Record::Atom(a) = &pdb.records[0] {
+            assert_eq!((a.x, a.y, a.z), (2.0, 3.0, 4.0));
+        } else {
+            panic!();
+        }
+    }
+
+    #[test]
+    fn test_merge_split() {
+        use super::*;
+        let part = |id_code: &str, serial: u32| {
+            let (_, pdb) = crate::parse(&format!(
+                "HEADER    RIBOSOME                                20-APR-99   {}\nMODEL     {:>4}\nATOM      1  N   ALA A   1      47.699  22.521  30.384  1.00 37.82           N\nATOM      2  CA  ALA A   1      48.661  22.866  29.347  1.00 27.31           C\nENDMDL\n",
+                id_code, serial
+            ))
+            .unwrap();
+            pdb
+        };
+        let mut merged = merge_split(vec![part("1VOQ", 1), part("1VOR", 1)]);
+        assert_eq!(merged.header().header().unwrap().id_code, "1VOQ");
+        assert_eq!(merged.records().len(), 3);
+        assert_eq!(
+            merged.models().iter().map(|m| m.serial).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            merged.models().iter().map(|m| m.atoms.len()).sum::<usize>(),
+            4
+        );
+    }
+
+    #[test]
+    fn ligands() {
+        use super::super::types::ResidueId;
+        let (_, pdb) = crate::parse(
+            r#"HET    HEM  A 142      43                                                       
+ATOM      1  N   VAL A   1      47.699  22.521  30.384  1.00 37.82           N
+HETATM    2 FE   HEM A 142      17.120  36.051  12.580  1.00 20.14          FE
+HETATM    3 NA   HEM A 142      18.120  36.051  12.580  1.00 20.14           N
+HETATM    4  O   HOH A 201      20.120  36.051  12.580  1.00 20.14           O
+HETATM    5  O   WAT A 202      21.120  36.051  12.580  1.00 20.14           O
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            pdb.ligands(),
+            vec![ResidueId {
+                chain_id: 'A',
+                residue_seq: 142,
+                insertion_code: None
+            }]
+        );
+    }
+
+    #[test]
+    fn heterogen_types() {
+        let (_, pdb) = crate::parse(
+            r#"HET    HEM  A 142      43                                                       
+HET    HEM  B 142      43                                                       
+HET    SO4  A 301       5     SULFATE                                           
+HETNAM     HEM PROTOPORPHYRIN IX CONTAINING FE
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            pdb.heterogen_types(),
+            vec![
+                (
+                    "HEM".to_owned(),
+                    "PROTOPORPHYRIN IX CONTAINING FE".to_owned()
+                ),
+                ("SO4".to_owned(), "SULFATE".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn header_diff() {
+        use super::{super::types::*, *};
+        let (_, a) = crate::parse(
+            r#"HEADER    OXYGEN TRANSPORT                        20-APR-99   1HHO
+COMPND    MOL_ID: 1;
+COMPND   2 MOLECULE: HEMOGLOBIN ALPHA CHAIN;
+COMPND   3 CHAIN: A, C
+"#,
+        )
+        .unwrap();
+        let (_, b) = crate::parse(
+            r#"HEADER    OXYGEN TRANSPORT                        20-APR-99   1HHO
+COMPND    MOL_ID: 1;
+COMPND   2 MOLECULE: HEMOGLOBIN BETA CHAIN;
+COMPND   3 CHAIN: A, C
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            diff_headers(&a, &b),
+            vec![HeaderDiff {
+                record: "COMPND",
+                mol_id: Some(1),
+                key: "MOLECULE".to_owned(),
+                change: HeaderChange::Changed {
+                    from: "HEMOGLOBIN ALPHA CHAIN".to_owned(),
+                    to: "HEMOGLOBIN BETA CHAIN".to_owned()
+                }
+            }]
+        );
+        assert!(diff_headers(&a, &a).is_empty());
+    }
+
+    #[test]
+    fn counts() {
+        let (_, pdb) = crate::parse(
+            r#"MODEL        1
+ATOM      1  N   MET A   1      47.699  22.521  30.384  1.00 37.82           N
+ATOM      2  CA  MET A   1      48.661  22.866  29.347  1.00 27.31           C
+ATOM      3  N   ALA A   2      49.699  22.521  30.384  1.00 37.82           N
+ATOM      4  N   MET B   1      57.699  22.521  30.384  1.00 37.82           N
+HETATM    5  O   HOH B 101      17.120  36.051  12.580  1.00 20.14           O
+ENDMDL
+MODEL        2
+ATOM      1  N   MET A   1      47.699  22.521  30.384  1.00 37.82           N
+ENDMDL
+"#,
+        )
+        .unwrap();
+        assert_eq!(pdb.atom_count(), 5);
+        assert_eq!(pdb.residue_count(), 4);
+        assert_eq!(pdb.chain_count(), 2);
+    }
+
+    #[test]
+    fn token_and_date_warnings() {
+        use super::super::types::ParseWarning;
+        use chrono::NaiveDate;
+        let (_, pdb) = crate::parse(
+            r#"HEADER    OXYGEN TRANSPORT                        20-APR-69   1HHO
+COMPND    MOL_ID: 1;
+COMPND   2 FLAVOR: STRAWBERRY;
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            pdb.warnings(),
+            vec![
+                ParseWarning::SuspiciousDate {
+                    record: "HEADER",
+                    date: NaiveDate::from_ymd_opt(2069, 4, 20).unwrap()
+                },
+                ParseWarning::UnknownToken {
+                    record: "COMPND",
+                    key: "FLAVOR".to_owned()
+                }
+            ]
+        );
+    }
 }
@@ -1,18 +1,117 @@
 use chrono::NaiveDate;
 use std::{marker::PhantomData, str::FromStr};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct Continuation<T> {
+    #[allow(dead_code)]
     pub continuation: u32,
     pub remaining: String,
+    /// line of the continuation within its record counted from 1, set while folding
+    #[allow(dead_code)]
+    pub line_number: Option<usize>,
     pub phantom: PhantomData<T>,
 }
 
+/// Four character identifier of a PDB entry. First character is a digit
+/// and the remaining characters are alphanumeric, e.g. `1ABC`
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IdCode(String);
+
+impl IdCode {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for IdCode {
+    type Err = String;
+    fn from_str(inp: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
+        let valid = inp.len() == 4
+            && inp.starts_with(|c: char| c.is_ascii_digit())
+            && inp.chars().all(|c| c.is_ascii_alphanumeric());
+        if valid {
+            Ok(IdCode(inp.to_owned()))
+        } else {
+            Err(format!("Invalid id code {}", inp))
+        }
+    }
+}
+
+impl std::fmt::Display for IdCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PartialEq<&str> for IdCode {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
 ///Holds name of an author utilized by multiple
 ///parsers such as author and journal author parsers
 #[derive(Debug, Clone, PartialEq)]
 pub struct Author(pub String);
 
+/// Sequence databases referred to by DBREF records
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Database {
+    /// GenBank
+    Gb,
+    Embl,
+    Pdb,
+    /// UniProt
+    Unp,
+    Norine,
+    Unimes,
+    Pir,
+    /// Swiss-Prot, used by older entries
+    Sws,
+    Trembl,
+    Other(String),
+}
+
+impl Default for Database {
+    fn default() -> Self {
+        Database::Other(String::new())
+    }
+}
+
+impl From<&str> for Database {
+    fn from(inp: &str) -> Self {
+        match inp {
+            "GB" => Database::Gb,
+            "EMBL" => Database::Embl,
+            "PDB" => Database::Pdb,
+            "UNP" => Database::Unp,
+            "NORINE" => Database::Norine,
+            "UNIMES" => Database::Unimes,
+            "PIR" => Database::Pir,
+            "SWS" => Database::Sws,
+            "TREMBL" => Database::Trembl,
+            other => Database::Other(other.to_owned()),
+        }
+    }
+}
+
+impl std::fmt::Display for Database {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            Database::Gb => "GB",
+            Database::Embl => "EMBL",
+            Database::Pdb => "PDB",
+            Database::Unp => "UNP",
+            Database::Norine => "NORINE",
+            Database::Unimes => "UNIMES",
+            Database::Pir => "PIR",
+            Database::Sws => "SWS",
+            Database::Trembl => "TREMBL",
+            Database::Other(s) => s,
+        })
+    }
+}
+
 /// Experimental techniques utilized in obtaining
 /// structure data
 #[derive(Debug, Clone, PartialEq)]
@@ -25,6 +124,8 @@ pub enum ExperimentalTechnique {
     SolidStateNmr,
     SolutionNmr,
     SolutionScattering,
+    /// computational model, not determined by an experiment
+    TheoreticalModel,
 }
 
 impl FromStr for ExperimentalTechnique {
@@ -39,27 +140,62 @@ impl FromStr for ExperimentalTechnique {
             "SOLID-STATE NMR" => Ok(ExperimentalTechnique::SolidStateNmr),
             "SOLUTION NMR" => Ok(ExperimentalTechnique::SolutionNmr),
             "SOLUTION SCATTERING" => Ok(ExperimentalTechnique::SolutionScattering),
+            "THEORETICAL MODEL" => Ok(ExperimentalTechnique::TheoreticalModel),
             _ => Err(format!("Unknown experimental result {}", inp)),
         }
     }
 }
 
+impl std::fmt::Display for ExperimentalTechnique {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ExperimentalTechnique::XRayDiffraction => "X-RAY DIFFRACTION",
+            ExperimentalTechnique::FiberDiffraction => "FIBER DIFFRACTION",
+            ExperimentalTechnique::NeutronDiffraction => "NEUTRON DIFFRACTION",
+            ExperimentalTechnique::ElectronCrystallography => "ELECTRON CRYSTALLOGRAPHY",
+            ExperimentalTechnique::ElectronMicroscopy => "ELECTRON MICROSCOPY",
+            ExperimentalTechnique::SolidStateNmr => "SOLID-STATE NMR",
+            ExperimentalTechnique::SolutionNmr => "SOLUTION NMR",
+            ExperimentalTechnique::SolutionScattering => "SOLUTION SCATTERING",
+            ExperimentalTechnique::TheoreticalModel => "THEORETICAL MODEL",
+        })
+    }
+}
+
+/// value of the SYNTHETIC token of SOURCE records, either a plain `YES`/`NO` or a
+/// description of how the molecule was made
+#[derive(Debug, PartialEq, Clone)]
+pub enum Synthetic {
+    Flag(bool),
+    Description(String),
+}
+
 /// Represents keys of CMPND and SOURCE records
 #[derive(Debug, PartialEq, Clone)]
 pub enum Token {
     MoleculeId(u32),
     Molecule(String),
-    Chain { identifiers: Vec<String> },
+    Chain {
+        identifiers: Vec<String>,
+    },
     Fragment(String),
-    Synonym { synonyms: Vec<String> },
-    Ec { commission_numbers: Vec<String> },
+    Synonym {
+        synonyms: Vec<String>,
+    },
+    Ec {
+        commission_numbers: Vec<String>,
+    },
     Engineered(bool),
     Mutation(bool),
     OtherDetails(String),
-    Synthetic(String),
+    Synthetic(Synthetic),
     OrganismScientific(String),
-    OrganismCommon { organisms: Vec<String> },
-    OrganismTaxId { id: Vec<u32> },
+    OrganismCommon {
+        organisms: Vec<String>,
+    },
+    OrganismTaxId {
+        id: Vec<u32>,
+    },
     Strain(String),
     Variant(String),
     CellLine(String),
@@ -71,10 +207,16 @@ pub enum Token {
     Secretion(String),
     CellularLocation(String),
     Plasmid(String),
-    Gene { gene: Vec<String> },
+    Gene {
+        gene: Vec<String>,
+    },
     ExpressionSystem(String),
-    ExpressionSystemCommon { systems: Vec<String> },
-    ExpressionSystemTaxId { id: Vec<u32> },
+    ExpressionSystemCommon {
+        systems: Vec<String>,
+    },
+    ExpressionSystemTaxId {
+        id: Vec<u32>,
+    },
     ExpressionSystemStrain(String),
     ExpressionSystemVariant(String),
     ExpressionSystemCellLine(String),
@@ -88,20 +230,133 @@ pub enum Token {
     ExpressionSystemVector(String),
     ExpressionSystemPlasmid(String),
     ExpressionSystemGene(String),
+    /// token whose key is not defined by the format, kept as written
+    Unknown {
+        key: String,
+        value: String,
+    },
 }
 
-/// Represents a modification made to this pdb entry.
-#[derive(Debug, Clone)]
+impl Token {
+    /// Human readable value of the token regardless of its variant. List values
+    /// are joined with `, ` and boolean values are rendered as `YES`/`NO`
+    pub fn value_string(&self) -> String {
+        let yes_no = |b: &bool| if *b { "YES" } else { "NO" }.to_owned();
+        let join_ids = |ids: &Vec<u32>| {
+            ids.iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match self {
+            Token::MoleculeId(id) | Token::Atcc(id) | Token::ExpressionSystemAtcc(id) => {
+                id.to_string()
+            }
+            Token::Chain { identifiers: v }
+            | Token::Synonym { synonyms: v }
+            | Token::Ec {
+                commission_numbers: v,
+            }
+            | Token::OrganismCommon { organisms: v }
+            | Token::Gene { gene: v }
+            | Token::ExpressionSystemCommon { systems: v } => v.join(", "),
+            Token::Engineered(b) | Token::Mutation(b) => yes_no(b),
+            Token::Synthetic(Synthetic::Flag(b)) => yes_no(b),
+            Token::Synthetic(Synthetic::Description(s)) => s.clone(),
+            Token::OrganismTaxId { id } | Token::ExpressionSystemTaxId { id } => join_ids(id),
+            Token::Molecule(s)
+            | Token::Fragment(s)
+            | Token::OtherDetails(s)
+            | Token::OrganismScientific(s)
+            | Token::Strain(s)
+            | Token::Variant(s)
+            | Token::CellLine(s)
+            | Token::Organ(s)
+            | Token::Tissue(s)
+            | Token::Cell(s)
+            | Token::Organelle(s)
+            | Token::Secretion(s)
+            | Token::CellularLocation(s)
+            | Token::Plasmid(s)
+            | Token::ExpressionSystem(s)
+            | Token::ExpressionSystemStrain(s)
+            | Token::ExpressionSystemVariant(s)
+            | Token::ExpressionSystemCellLine(s)
+            | Token::ExpressionSystemOrgan(s)
+            | Token::ExpressionSystemTissue(s)
+            | Token::ExpressionSystemCell(s)
+            | Token::ExpressionSystemOrganelle(s)
+            | Token::ExpressionSystemCellularLocation(s)
+            | Token::ExpressionSystemVectorType(s)
+            | Token::ExpressionSystemVector(s)
+            | Token::ExpressionSystemPlasmid(s)
+            | Token::ExpressionSystemGene(s)
+            | Token::Unknown { value: s, .. } => s.clone(),
+        }
+    }
+
+    /// Key of the token as written in COMPND and SOURCE records, such as `MOL_ID`
+    pub fn key(&self) -> &str {
+        match self {
+            Token::MoleculeId(_) => "MOL_ID",
+            Token::Molecule(_) => "MOLECULE",
+            Token::Chain { .. } => "CHAIN",
+            Token::Fragment(_) => "FRAGMENT",
+            Token::Synonym { .. } => "SYNONYM",
+            Token::Ec { .. } => "EC",
+            Token::Engineered(_) => "ENGINEERED",
+            Token::Mutation(_) => "MUTATION",
+            Token::OtherDetails(_) => "OTHER_DETAILS",
+            Token::Synthetic(_) => "SYNTHETIC",
+            Token::OrganismScientific(_) => "ORGANISM_SCIENTIFIC",
+            Token::OrganismCommon { .. } => "ORGANISM_COMMON",
+            Token::OrganismTaxId { .. } => "ORGANISM_TAXID",
+            Token::Strain(_) => "STRAIN",
+            Token::Variant(_) => "VARIANT",
+            Token::CellLine(_) => "CELL_LINE",
+            Token::Atcc(_) => "ATCC",
+            Token::Organ(_) => "ORGAN",
+            Token::Tissue(_) => "TISSUE",
+            Token::Cell(_) => "CELL",
+            Token::Organelle(_) => "ORGANELLE",
+            Token::Secretion(_) => "SECRETION",
+            Token::CellularLocation(_) => "CELLULAR_LOCATION",
+            Token::Plasmid(_) => "PLASMID",
+            Token::Gene { .. } => "GENE",
+            Token::ExpressionSystem(_) => "EXPRESSION_SYSTEM",
+            Token::ExpressionSystemCommon { .. } => "EXPRESSION_SYSTEM_COMMON",
+            Token::ExpressionSystemTaxId { .. } => "EXPRESSION_SYSTEM_TAXID",
+            Token::ExpressionSystemStrain(_) => "EXPRESSION_SYSTEM_STRAIN",
+            Token::ExpressionSystemVariant(_) => "EXPRESSION_SYSTEM_VARIANT",
+            Token::ExpressionSystemCellLine(_) => "EXPRESSION_SYSTEM_CELL_LINE",
+            Token::ExpressionSystemAtcc(_) => "EXPRESSION_SYSTEM_ATCC_NUMBER",
+            Token::ExpressionSystemOrgan(_) => "EXPRESSION_SYSTEM_ORGAN",
+            Token::ExpressionSystemTissue(_) => "EXPRESSION_SYSTEM_TISSUE",
+            Token::ExpressionSystemCell(_) => "EXPRESSION_SYSTEM_CELL",
+            Token::ExpressionSystemOrganelle(_) => "EXPRESSION_SYSTEM_ORGANELLE",
+            Token::ExpressionSystemCellularLocation(_) => "EXPRESSION_SYSTEM_CELLULAR_LOCATION",
+            Token::ExpressionSystemVectorType(_) => "EXPRESSION_SYSTEM_VECTOR_TYPE",
+            Token::ExpressionSystemVector(_) => "EXPRESSION_SYSTEM_VECTOR",
+            Token::ExpressionSystemPlasmid(_) => "EXPRESSION_SYSTEM_PLASMID",
+            Token::ExpressionSystemGene(_) => "EXPRESSION_SYSTEM_GENE",
+            Token::Unknown { key, .. } => key,
+        }
+    }
+}
+
+/// Represents a modification made to this pdb entry. Revisions are
+/// ordered by modification number and then by modification date.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Revdat {
     pub modification_number: u32,
     pub modification_date: NaiveDate,
-    pub idcode: String,
+    pub idcode: IdCode,
     pub modification_type: ModificationType,
     pub modification_detail: Vec<String>,
 }
 
 /// modification type of REVDAT record
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ModificationType {
     /// initial release of the entry. Indicated as 0
     /// in a REVDAT record
@@ -121,41 +376,41 @@ pub enum SerialNumber {
 }
 
 /// contains HEADER recor information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Header {
     pub classification: String,
     pub deposition_date: NaiveDate,
-    pub id_code: String,
+    pub id_code: IdCode,
 }
 
 impl std::default::Default for Header {
     fn default() -> Self {
         Header {
             classification: String::default(),
-            deposition_date: NaiveDate::from_ymd(1900, 1, 1),
-            id_code: String::default(),
+            deposition_date: NaiveDate::from_ymd_opt(1900, 1, 1).unwrap(),
+            id_code: IdCode::default(),
         }
     }
 }
 
 /// result of a TITLE record
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Title {
     pub title: String,
 }
 
 /// contains pdb entry ids which removed
 /// this one from PDB
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Obslte {
     pub replacement_date: NaiveDate,
-    pub replacement_ids: Vec<String>,
+    pub replacement_ids: Vec<IdCode>,
 }
 
 impl std::default::Default for Obslte {
     fn default() -> Self {
         Obslte {
-            replacement_date: NaiveDate::from_ymd(1900, 1, 1),
+            replacement_date: NaiveDate::from_ymd_opt(1900, 1, 1).unwrap(),
             replacement_ids: Vec::new(),
         }
     }
@@ -164,98 +419,126 @@ impl std::default::Default for Obslte {
 /// if this entry is a part of bigger
 /// structure, this struct holds ids of other
 /// parts of the bigger structure
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Split {
     pub id_codes: Vec<String>,
 }
 
 /// fallacies of this entry
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Caveat {
-    pub id_code: String,
+    /// id code of the entry the caveat applies to, usually the entry itself
+    pub id_code: IdCode,
     pub comment: String,
 }
 
 /// pdb entry ids made obsolete by this entry
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Sprsde {
     pub sprsde_date: NaiveDate,
-    pub id_code: String,
-    pub superseeded: Vec<String>,
+    pub id_code: IdCode,
+    pub superseeded: Vec<IdCode>,
 }
 
 impl std::default::Default for Sprsde {
     fn default() -> Self {
         Sprsde {
-            sprsde_date: NaiveDate::from_ymd(1900, 1, 1),
+            sprsde_date: NaiveDate::from_ymd_opt(1900, 1, 1).unwrap(),
             superseeded: Vec::new(),
-            id_code: String::default(),
+            id_code: IdCode::default(),
         }
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Seqres {
     pub chain_id: Option<char>,
+    /// number of residues in the chain as given by numRes column
+    pub num_res: u32,
+    /// serNum of each SEQRES line folded into the record, in the order read
+    pub serial_numbers: Vec<u32>,
     pub residues: Vec<String>,
 }
 
 /// model type of the entry
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Mdltyp {
     pub structural_annotation: Vec<String>,
 }
 
 /// collection of revisions
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Revdats {
     pub revdat: Vec<Revdat>,
 }
 
 /// collection of tokens in a CMPND record
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Cmpnd {
     pub tokens: Vec<Token>,
 }
 
+impl Cmpnd {
+    /// iterates tokens in the order they appear in the record
+    pub fn iter(&self) -> std::slice::Iter<'_, Token> {
+        self.tokens.iter()
+    }
+}
+
+impl IntoIterator for Cmpnd {
+    type Item = Token;
+    type IntoIter = std::vec::IntoIter<Token>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.tokens.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Cmpnd {
+    type Item = &'a Token;
+    type IntoIter = std::slice::Iter<'a, Token>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.tokens.iter()
+    }
+}
+
 /// collection of tokens in a SOURCE record
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Source {
     pub tokens: Vec<Token>,
 }
 
 /// keywords related to the entry
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Keywds {
     pub keywords: Vec<String>,
 }
 
 /// author collection
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Authors {
     pub authors: Vec<Author>,
 }
 
 /// journal author collection
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct JournalAuthors {
     pub authors: Vec<Author>,
 }
 
 /// journal title
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct JournalTitle {
     pub title: String,
 }
 
 /// journal editor collection
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct JournalEditors {
     pub name: Vec<Author>,
 }
 
 /// journal reference
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct JournalReference {
     pub publication_name: String,
     pub volume: Option<u32>,
@@ -264,53 +547,53 @@ pub struct JournalReference {
 }
 
 /// journal Citation fields
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct JournalCitation {
     pub serial_type: Option<SerialNumber>,
     pub serial: Option<String>,
 }
 
 /// journal publication fields
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct JournalPublication {
     pub publication: String,
 }
 
 /// journal PubMed id
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct JournalPubMedId {
     pub id: u32,
 }
 
 /// digital object identifier of related e-pub
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct JournalDoi {
     pub id: String,
 }
 
 /// experimanetal techniques used for exploring
 /// structure of this entry
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Experimental {
     pub techniques: Vec<ExperimentalTechnique>,
 }
 
 /// number of models in this file
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Nummdl {
     pub num: u32,
 }
 
 /// cross references to other sequence databases
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Dbref {
-    pub idcode: String,
+    pub idcode: IdCode,
     pub chain_id: char,
-    pub seq_begin: u32,
-    pub initial_sequence: Option<char>,
-    pub seq_end: u32,
-    pub ending_sequence: Option<char>,
-    pub database: String,
+    /// first residue of the referenced segment, seqBegin and insertBegin columns
+    pub begin: ResidueId,
+    /// last residue of the referenced segment, seqEnd and insertEnd columns
+    pub end: ResidueId,
+    pub database: Database,
     pub db_accession: String,
     pub db_idcode: String,
     pub db_seq_begin: u32,
@@ -319,43 +602,547 @@ pub struct Dbref {
     pub dbins_end: Option<char>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Dbref1 {
-    pub idcode: String,
+    pub idcode: IdCode,
     pub chain_id: char,
-    pub seq_begin: u32,
-    pub initial_sequence: Option<char>,
-    pub seq_end: u32,
-    pub ending_sequence: Option<char>,
-    pub database: String,
+    /// first residue of the referenced segment, seqBegin and insertBegin columns
+    pub begin: ResidueId,
+    /// last residue of the referenced segment, seqEnd and insertEnd columns
+    pub end: ResidueId,
+    pub database: Database,
     pub db_idcode: String,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Dbref2 {
-    pub idcode: String,
+    pub idcode: IdCode,
     pub chain_id: char,
     pub db_accession: String,
     pub db_seq_begin: u32,
     pub db_seq_end: u32,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Seqadv {
     pub idcode: String,
     pub conflicting_residue: String,
     pub chain_id: char,
-    pub sequence_number: u32,
+    pub sequence_number: i32,
     pub insertion_code: Option<char>,
     pub database: String,
     pub db_accession: String,
     pub sequence_db_residue: Option<String>,
     pub sequence_db_sequence_number: Option<u32>,
-    pub conflict: String,
+    pub conflict: SeqAdvConflict,
+}
+
+/// Reason of a difference between the SEQRES sequence and the sequence database
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SeqAdvConflict {
+    EngineeredMutation,
+    CloningArtifact,
+    ExpressionTag,
+    Variant,
+    Conflict,
+    Deletion,
+    Insertion,
+    Microheterogeneity,
+    SeeRemark999,
+    Other(String),
+}
+
+impl Default for SeqAdvConflict {
+    fn default() -> Self {
+        SeqAdvConflict::Other(String::new())
+    }
+}
+
+impl From<&str> for SeqAdvConflict {
+    fn from(inp: &str) -> Self {
+        match inp {
+            "ENGINEERED MUTATION" | "ENGINEERED" => SeqAdvConflict::EngineeredMutation,
+            "CLONING ARTIFACT" => SeqAdvConflict::CloningArtifact,
+            "EXPRESSION TAG" => SeqAdvConflict::ExpressionTag,
+            "VARIANT" => SeqAdvConflict::Variant,
+            "CONFLICT" => SeqAdvConflict::Conflict,
+            "DELETION" => SeqAdvConflict::Deletion,
+            "INSERTION" => SeqAdvConflict::Insertion,
+            "MICROHETEROGENEITY" => SeqAdvConflict::Microheterogeneity,
+            "SEE REMARK 999" => SeqAdvConflict::SeeRemark999,
+            other => SeqAdvConflict::Other(other.to_owned()),
+        }
+    }
+}
+
+impl std::fmt::Display for SeqAdvConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SeqAdvConflict::EngineeredMutation => "ENGINEERED MUTATION",
+            SeqAdvConflict::CloningArtifact => "CLONING ARTIFACT",
+            SeqAdvConflict::ExpressionTag => "EXPRESSION TAG",
+            SeqAdvConflict::Variant => "VARIANT",
+            SeqAdvConflict::Conflict => "CONFLICT",
+            SeqAdvConflict::Deletion => "DELETION",
+            SeqAdvConflict::Insertion => "INSERTION",
+            SeqAdvConflict::Microheterogeneity => "MICROHETEROGENEITY",
+            SeqAdvConflict::SeeRemark999 => "SEE REMARK 999",
+            SeqAdvConflict::Other(s) => s,
+        })
+    }
+}
+/// a single atom of the coordinate section. Both ATOM and HETATM
+/// records are parsed into this struct, `hetero` tells them apart
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Atom {
+    pub serial: u32,
+    /// atom name with surrounding spaces removed
+    pub name: String,
+    /// atom name columns 13-16 as they appear in the file. Alignment is meaningful,
+    /// the element is right justified in columns 13-14
+    pub name_field: String,
+    pub alt_loc: Option<char>,
+    pub residue_name: String,
+    pub chain_id: char,
+    pub residue_seq: i32,
+    pub insertion_code: Option<char>,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub occupancy: f64,
+    pub temp_factor: f64,
+    /// segment identifier of columns 73-76, written by CHARMM and similar programs
+    pub segment_id: Option<String>,
+    /// element symbol with usual capitalization, such as `C` or `Fe`
+    pub element: String,
+    pub charge: Option<String>,
+    pub hetero: bool,
+    /// anisotropic temperature factors U11, U22, U33, U12, U13, U23 scaled by 10^4,
+    /// read from the ANISOU record following the atom
+    pub anisou: Option<[i32; 6]>,
+}
+
+/// identifies a residue within the coordinate section
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ResidueId {
+    pub chain_id: char,
+    pub residue_seq: i32,
+    pub insertion_code: Option<char>,
+}
+
+/// a single structure of the coordinate section. Entries without MODEL records
+/// have one implicit model numbered 1
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Model {
+    pub serial: u32,
+    pub atoms: Vec<Atom>,
+}
+
+/// end of a chain in the coordinate section
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Ter {
+    pub serial: Option<u32>,
 }
+
+/// connectivity of an atom to the atoms it is bonded with
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Conect {
+    pub serial: u32,
+    pub bonded: Vec<u32>,
+}
+
+/// unit cell parameters, space group and Z value of a crystal structure. Entries not
+/// determined by crystallography list a unit cube with space group `P 1` and Z of 1
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Cryst1 {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub alpha: f64,
+    pub beta: f64,
+    pub gamma: f64,
+    pub space_group: String,
+    /// number of polymeric chains in a unit cell
+    pub z: u32,
+}
+
+/// transformation from orthogonal coordinates to the submitted coordinates
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Origx {
+    pub matrix: [[f64; 3]; 3],
+    pub translation: [f64; 3],
+}
+
+/// transformation from orthogonal coordinates to fractional crystallographic coordinates
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Scale {
+    pub matrix: [[f64; 3]; 3],
+    pub translation: [f64; 3],
+}
+
+/// transformation expressing non-crystallographic symmetry
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Mtrix {
+    pub serial: u32,
+    pub matrix: [[f64; 3]; 3],
+    pub translation: [f64; 3],
+    /// true if coordinates generated by this transformation are present in the file
+    pub given: bool,
+}
+
+/// numbers of lines of several record types in the entry, given in the MASTER record
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Master {
+    pub num_remark: u32,
+    pub num_het: u32,
+    pub num_helix: u32,
+    pub num_sheet: u32,
+    pub num_site: u32,
+    /// number of ORIGX, SCALE and MTRIX lines
+    pub num_xform: u32,
+    /// number of ATOM and HETATM lines
+    pub num_coord: u32,
+    pub num_ter: u32,
+    pub num_conect: u32,
+    pub num_seq: u32,
+}
+
+/// nature of a bond listed in a CONECT record, judged by the distance of its atoms
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BondKind {
+    /// atoms are close enough to share electrons
+    Covalent,
+    /// atoms are too far apart for a covalent bond, such as a hydrogen bond or salt bridge
+    LongRange,
+    /// one of the atoms has no coordinates
+    Unresolved,
+}
+
+/// non-standard residue, a heterogen, present in the entry
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Het {
+    pub het_id: String,
+    pub chain_id: char,
+    pub residue_seq: i32,
+    pub insertion_code: Option<char>,
+    /// number of HETATM records of the group
+    pub num_het_atoms: u32,
+    /// short description of the heterogen
+    pub text: Option<String>,
+}
+
+/// chemical name of a heterogen
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HetName {
+    pub het_id: String,
+    pub name: String,
+}
+
+/// chemical formula of a heterogen
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Formul {
+    pub component_number: u32,
+    pub het_id: String,
+    /// set for water, marked by an asterisk
+    pub water: bool,
+    /// formula folded from all continuation lines, such as `8(C8 H15 N O6)`
+    pub formula: String,
+}
+
+/// helix of the secondary structure, given by its initial and terminal residues
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Helix {
+    pub serial: u32,
+    pub helix_id: String,
+    pub init_residue_name: String,
+    pub init_chain_id: char,
+    pub init_residue_seq: i32,
+    pub init_insertion_code: Option<char>,
+    pub end_residue_name: String,
+    pub end_chain_id: char,
+    pub end_residue_seq: i32,
+    pub end_insertion_code: Option<char>,
+    /// class of the helix, 1 for right-handed alpha helices
+    pub helix_class: Option<u32>,
+    pub comment: Option<String>,
+    /// length as given in the length column of the record
+    pub declared_length: Option<i32>,
+}
+
+/// strand of a sheet, given by its initial and terminal residues
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Sheet {
+    /// number of the strand within its sheet, starting from 1
+    pub strand: u32,
+    pub sheet_id: String,
+    pub num_strands: u32,
+    pub init_residue_name: String,
+    pub init_chain_id: char,
+    pub init_residue_seq: i32,
+    pub init_insertion_code: Option<char>,
+    pub end_residue_name: String,
+    pub end_chain_id: char,
+    pub end_residue_seq: i32,
+    pub end_insertion_code: Option<char>,
+    /// sense with respect to the previous strand, 0 for the first strand, 1 if parallel
+    /// and -1 if anti-parallel
+    pub sense: i32,
+}
+
+/// secondary structure assigned to a residue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SsType {
+    Helix,
+    Sheet,
+    /// TURN records were removed from the format, hence this is never assigned by
+    /// parsed records
+    Turn,
+    Coil,
+}
+
+/// disulfide bond between the two cysteine residues
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SsBond {
+    pub serial: u32,
+    pub residue_name_1: String,
+    pub chain_id_1: char,
+    pub residue_seq_1: i32,
+    pub insertion_code_1: Option<char>,
+    pub residue_name_2: String,
+    pub chain_id_2: char,
+    pub residue_seq_2: i32,
+    pub insertion_code_2: Option<char>,
+    /// symmetry operator applied to the first residue, such as `1555`
+    pub symmetry_1: Option<String>,
+    pub symmetry_2: Option<String>,
+    /// bond distance in angstroms
+    pub length: Option<f64>,
+}
+
+/// group of residues forming a site of the molecule
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Site {
+    pub id: String,
+    /// number of residues of the site as given by the numRes column
+    pub num_residues: u32,
+    /// residue names and ids of the site
+    pub residues: Vec<(String, ResidueId)>,
+}
+
+/// biological meaning of a site, given in REMARK 800
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SiteDescription {
+    pub id: String,
+    pub evidence: String,
+    pub description: String,
+}
+
+/// transformation of REMARK 350 generating a copy of the asymmetric unit
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Biomt {
+    pub serial: u32,
+    pub matrix: [[f64; 3]; 3],
+    pub translation: [f64; 3],
+}
+
+/// chains of an APPLY THE FOLLOWING TO CHAINS line of REMARK 350, together with the
+/// operators listed after it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AssemblyGroup {
+    pub chains: Vec<String>,
+    pub operators: Vec<Biomt>,
+}
+
+/// biological assembly of REMARK 350, built by applying the operators of each group to
+/// the chains of that group
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BiologicalAssembly {
+    /// number given on the BIOMOLECULE line
+    pub id: u32,
+    pub groups: Vec<AssemblyGroup>,
+}
+
+/// peptide bond of two consecutive residues in cis conformation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CisPep {
+    pub serial: u32,
+    pub residue_name_1: String,
+    pub chain_id_1: char,
+    pub residue_seq_1: i32,
+    pub insertion_code_1: Option<char>,
+    pub residue_name_2: String,
+    pub chain_id_2: char,
+    pub residue_seq_2: i32,
+    pub insertion_code_2: Option<char>,
+    /// model the bond belongs to, 0 for entries having a single model
+    pub model_number: u32,
+    /// measured omega angle in degrees
+    pub measure: f64,
+}
+
+/// residue listed in REMARK 465 as not located in the experiment
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MissingResidue {
+    /// model the residue is missing from, `None` when missing from all models
+    pub model: Option<u32>,
+    pub residue_name: String,
+    pub residue_id: ResidueId,
+}
+
+/// resolution given in REMARK 2, `None` when not applicable
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Resolution {
+    pub angstroms: Option<f64>,
+}
+
+/// version of the PDB format the entry complies with, given in REMARK 4
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FormatVersion {
+    /// version number such as `3.30`, `None` when the block does not state it
+    pub format_version: Option<String>,
+}
+
+/// refinement statistics given in REMARK 3
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RefinementStats {
+    /// R value of the working set
+    pub r_value: Option<f64>,
+    /// R value of the test set excluded from refinement
+    pub r_free: Option<f64>,
+}
+
+/// legacy footnote of older pdb files
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FootNote {
+    pub number: u32,
+    pub text: String,
+}
+
+/// kind of polymer a chain is made of, see
+/// [polymer_type](../../residue/fn.polymer_type.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolymerType {
+    Protein,
+    Dna,
+    Rna,
+    Unknown,
+}
+
+/// lines of the original input a record was parsed from, see
+/// [records_with_raw](../pdb_file/struct.PdbFile.html#method.records_with_raw)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RawLines {
+    /// line the record starts at, numbered from 1
+    pub line: usize,
+    /// lines of the record as they appear in the input, without line endings. Blank
+    /// lines preceding the record are left out
+    pub lines: Vec<String>,
+}
+
+/// non fatal issues found while parsing or validating an entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseWarning {
+    /// taxonomy id which is zero or beyond any id assigned by NCBI
+    ImplausibleTaxId(u32),
+    /// count given in a record disagrees with the number of items listed
+    CountMismatch {
+        record: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// numeric resolution given for an entry determined by NMR only
+    NmrWithResolution(f64),
+    /// length column of a HELIX record differing from its residue range
+    HelixLengthMismatch {
+        serial: u32,
+        declared: i32,
+        computed: i32,
+    },
+    /// Z value of 0 in the CRYST1 record of an entry determined by crystallography
+    ZeroMoleculesPerCell,
+    /// SEQRES lines of a chain whose serNum does not follow the previous line
+    SeqresSerialOutOfOrder {
+        chain_id: Option<char>,
+        expected: u32,
+        found: u32,
+    },
+    /// model serials of an ensemble skip a number, often a sign of truncation
+    ModelGap { missing: u32 },
+    /// chain having polymer atoms but no SEQRES records
+    ChainWithoutSeqres(char),
+    /// chain named in an annotation record but absent from the coordinate section
+    UnknownChain {
+        record: &'static str,
+        chain_id: char,
+    },
+    /// REVDAT modification whose id code differs from the HEADER id code
+    RevdatIdCodeMismatch {
+        modification_number: u32,
+        id_code: String,
+    },
+    /// record appearing after records of a later section of the file
+    RecordOutOfOrder {
+        record: &'static str,
+        after: &'static str,
+    },
+    /// input from line `line`, numbered from 1, could not be parsed and was left out
+    UnparsedInput { line: usize },
+    /// line `line`, numbered from 1, is longer than the `max_line_length` option allows.
+    /// Input is cut before it
+    LineTooLong { line: usize, length: usize },
+    /// number of `record` lines given in the MASTER record disagrees with the entry
+    MasterCountMismatch {
+        record: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// COMPND or SOURCE token whose key is not defined by the format
+    UnknownToken { record: &'static str, key: String },
+    /// date lying before the first PDB deposition or in the future
+    SuspiciousDate {
+        record: &'static str,
+        date: NaiveDate,
+    },
+}
+
+/// groups issues of a [ValidationReport](struct.ValidationReport.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IssueCategory {
+    /// counts given in records disagree with the items present
+    Counts,
+    /// sequence and coordinate sections disagree
+    Consistency,
+    /// records are not in the order required by the format
+    Ordering,
+    /// experimental method disagrees with other annotations
+    Method,
+    /// values outside of their plausible range
+    Plausibility,
+}
+
+/// result of [validate](../../validate/fn.validate.html), all issues found in an entry
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    pub issues: Vec<(IssueCategory, ParseWarning)>,
+}
+
+impl ValidationReport {
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// issues of the given category
+    pub fn category(&self, category: IssueCategory) -> impl Iterator<Item = &ParseWarning> {
+        self.issues
+            .iter()
+            .filter(move |(c, _)| *c == category)
+            .map(|(_, w)| w)
+    }
+}
+
 /// main enum unifying all record parser results.
 /// all sub parsers return a cariant of this
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Record {
     Header(Header),
     Title(Title),
@@ -384,5 +1171,201 @@ pub enum Record {
     Dbref1(Dbref1),
     Dbref2(Dbref2),
     Seqadv(Seqadv),
+    Atom(Atom),
+    FootNote(FootNote),
+    Het(Het),
+    HetName(HetName),
+    Formul(Formul),
+    Helix(Helix),
+    Sheet(Sheet),
+    SsBond(SsBond),
+    CisPep(CisPep),
+    Site(Site),
+    Model(Model),
+    Ter(Ter),
+    Conect(Conect),
+    Cryst1(Cryst1),
+    Origx(Origx),
+    Scale(Scale),
+    Mtrix(Mtrix),
+    Resolution(Resolution),
+    FormatVersion(FormatVersion),
+    RefinementStats(RefinementStats),
+    SiteDescriptions(Vec<SiteDescription>),
+    BiologicalAssemblies(Vec<BiologicalAssembly>),
+    MissingResidues(Vec<MissingResidue>),
+    Master(Master),
+    /// REMARK line not read into one of the variants above, holding the text following
+    /// the record name with trailing spaces removed
+    Remark(String),
+}
+
+impl Record {
+    /// kind of the record
+    pub fn kind(&self) -> RecordKind {
+        match self {
+            Record::Header(_) => RecordKind::Header,
+            Record::Title(_) => RecordKind::Title,
+            Record::Obslte(_) => RecordKind::Obslte,
+            Record::Split(_) => RecordKind::Split,
+            Record::Caveat(_) => RecordKind::Caveat,
+            Record::Sprsde(_) => RecordKind::Sprsde,
+            Record::Seqres(_) => RecordKind::Seqres,
+            Record::Mdltyp(_) => RecordKind::Mdltyp,
+            Record::Revdats(_) => RecordKind::Revdats,
+            Record::Cmpnd(_) => RecordKind::Cmpnd,
+            Record::Source(_) => RecordKind::Source,
+            Record::Keywds(_) => RecordKind::Keywds,
+            Record::JournalAuthors(_) => RecordKind::JournalAuthors,
+            Record::JournalTitle(_) => RecordKind::JournalTitle,
+            Record::JournalEditors(_) => RecordKind::JournalEditors,
+            Record::JournalReference(_) => RecordKind::JournalReference,
+            Record::JournalCitation(_) => RecordKind::JournalCitation,
+            Record::JournalPublication(_) => RecordKind::JournalPublication,
+            Record::JournalPubMedId(_) => RecordKind::JournalPubMedId,
+            Record::JournalDoi(_) => RecordKind::JournalDoi,
+            Record::Experimental(_) => RecordKind::Experimental,
+            Record::Nummdl(_) => RecordKind::Nummdl,
+            Record::Authors(_) => RecordKind::Authors,
+            Record::Dbref(_) => RecordKind::Dbref,
+            Record::Dbref1(_) | Record::Dbref2(_) => RecordKind::Dbref,
+            Record::Seqadv(_) => RecordKind::Seqadv,
+            Record::Atom(_) => RecordKind::Atom,
+            Record::FootNote(_) => RecordKind::FootNote,
+            Record::Het(_) => RecordKind::Het,
+            Record::HetName(_) => RecordKind::HetName,
+            Record::Formul(_) => RecordKind::Formul,
+            Record::Helix(_) => RecordKind::Helix,
+            Record::Sheet(_) => RecordKind::Sheet,
+            Record::SsBond(_) => RecordKind::SsBond,
+            Record::CisPep(_) => RecordKind::CisPep,
+            Record::Site(_) => RecordKind::Site,
+            Record::Model(_) => RecordKind::Model,
+            Record::Ter(_) => RecordKind::Ter,
+            Record::Conect(_) => RecordKind::Conect,
+            Record::Cryst1(_) => RecordKind::Cryst1,
+            Record::Origx(_) => RecordKind::Origx,
+            Record::Scale(_) => RecordKind::Scale,
+            Record::Mtrix(_) => RecordKind::Mtrix,
+            Record::Resolution(_) => RecordKind::Resolution,
+            Record::FormatVersion(_) => RecordKind::FormatVersion,
+            Record::RefinementStats(_) => RecordKind::RefinementStats,
+            Record::SiteDescriptions(_) => RecordKind::SiteDescriptions,
+            Record::BiologicalAssemblies(_) => RecordKind::BiologicalAssemblies,
+            Record::MissingResidues(_) => RecordKind::MissingResidues,
+            Record::Master(_) => RecordKind::Master,
+            Record::Remark(_) => RecordKind::Remark,
+        }
+    }
+}
+
+/// how an item differs between two entries compared by
+/// [diff_headers](../pdb_file/fn.diff_headers.html)
+#[derive(Debug, Clone, PartialEq)]
+pub enum HeaderChange {
+    /// item present only in the second entry
+    Added(String),
+    /// item present only in the first entry
+    Removed(String),
+    /// item present in both entries with different values
+    Changed { from: String, to: String },
+}
+
+/// single difference between the headers of two entries
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeaderDiff {
+    /// name of the record holding the item, such as `COMPND`
+    pub record: &'static str,
+    /// MOL_ID of the molecule a COMPND or SOURCE token describes
+    pub mol_id: Option<u32>,
+    /// field of HEADER or TITLE, or key of a COMPND or SOURCE token
+    pub key: String,
+    pub change: HeaderChange,
+}
+
+/// variant of a [Record](enum.Record.html) without its data, used to select records by type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecordKind {
+    Header,
+    Title,
+    Obslte,
+    Split,
+    Caveat,
+    Sprsde,
+    Seqres,
+    Mdltyp,
+    Revdats,
+    Cmpnd,
+    Source,
+    Keywds,
+    JournalAuthors,
+    JournalTitle,
+    JournalEditors,
+    JournalReference,
+    JournalCitation,
+    JournalPublication,
+    JournalPubMedId,
+    JournalDoi,
+    Experimental,
+    Nummdl,
+    Authors,
+    /// DBREF records, along with DBREF1 and DBREF2 pairs which are merged into a single
+    /// `Record::Dbref` while parsing
+    Dbref,
+    Seqadv,
+    Atom,
+    FootNote,
+    Het,
+    HetName,
+    Formul,
+    Helix,
+    Sheet,
+    SsBond,
+    CisPep,
+    Site,
+    Model,
+    Ter,
+    Conect,
+    Cryst1,
+    Origx,
+    Scale,
+    Mtrix,
+    Resolution,
+    FormatVersion,
+    RefinementStats,
+    SiteDescriptions,
+    BiologicalAssemblies,
+    MissingResidues,
+    Master,
     Remark,
 }
+
+#[cfg(test)]
+mod test {
+    use super::{IdCode, Token};
+
+    #[test]
+    fn token_value_string() {
+        assert_eq!(
+            Token::Molecule("HEMOGLOBIN ALPHA CHAIN".to_owned()).value_string(),
+            "HEMOGLOBIN ALPHA CHAIN"
+        );
+        assert_eq!(
+            Token::Chain {
+                identifiers: vec!["A".to_owned(), "C".to_owned()]
+            }
+            .value_string(),
+            "A, C"
+        );
+        assert_eq!(Token::Engineered(true).value_string(), "YES");
+        assert_eq!(Token::Mutation(false).value_string(), "NO");
+    }
+
+    #[test]
+    fn idcode() {
+        assert_eq!("1ABC".parse::<IdCode>().unwrap(), "1ABC");
+        assert_eq!("12AB".parse::<IdCode>().unwrap(), "12AB");
+        assert!("ABCD".parse::<IdCode>().is_err());
+        assert!("1AB".parse::<IdCode>().is_err());
+    }
+}
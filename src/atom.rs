@@ -0,0 +1,685 @@
+/*!
+Contains parsers related to [Atom](http://www.wwpdb.org/documentation/file-format-content/format33/sect9.html#ATOM)
+and [Hetatm](http://www.wwpdb.org/documentation/file-format-content/format33/sect9.html#HETATM) records.
+These records present the atomic coordinates of the coordinate section, ATOM for standard residues and
+HETATM for atoms within non-standard groups.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{
+    alt, call,
+    character::complete::{anychar, line_ending},
+    combinator::{opt, verify},
+    do_parse, map_opt, named, peek,
+    sequence::tuple,
+    tag, take, take_str, IResult,
+};
+
+use std::{collections::HashMap, str};
+
+/// columns occupancy and tempFactor may be shifted by from their canonical positions
+pub const COLUMN_TOLERANCE: usize = 1;
+
+/// occupancy and tempFactor from `rest`, the columns following z. Values ending within
+/// [COLUMN_TOLERANCE](constant.COLUMN_TOLERANCE.html) columns of their canonical end are
+/// accepted, values without two decimals are read from their canonical columns
+fn occupancy_temp_factor(rest: &[u8]) -> Option<(f64, f64)> {
+    let shifted = shifted_real(2)(rest).and_then(|(after_occupancy, occupancy)| {
+        let (after_temp_factor, temp_factor) = shifted_real(2)(after_occupancy)?;
+        Ok((
+            occupancy,
+            temp_factor,
+            rest.len() - after_occupancy.len(),
+            rest.len() - after_temp_factor.len(),
+        ))
+    });
+    match shifted {
+        Ok((occupancy, temp_factor, occupancy_end, temp_factor_end))
+            if occupancy_end.abs_diff(6) <= COLUMN_TOLERANCE
+                && temp_factor_end.abs_diff(12) <= COLUMN_TOLERANCE =>
+        {
+            Some((occupancy, temp_factor))
+        }
+        _ => tuple((real(6), real(6)))(rest)
+            .ok()
+            .map(|(_, values)| values),
+    }
+}
+
+/// element symbol with its usual capitalization, `FE` becoming `Fe`
+fn normalize_element(symbol: String) -> String {
+    let mut chars = symbol.chars();
+    chars
+        .next()
+        .map(|first| {
+            first
+                .to_uppercase()
+                .chain(chars.flat_map(char::to_lowercase))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn column_field(rest: &[u8], start: usize, end: usize) -> Option<String> {
+    rest.get(start..end.min(rest.len()))
+        .and_then(|field| str::from_utf8(field).ok())
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .map(String::from)
+}
+
+named!(
+    #[doc=r#"Parses a single ATOM or HETATM record. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing an [Atom](../ast/types/struct.Atom.html) instance. HETATM records set `hetero` of the atom.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE    | FIELD       | DEFINITION                                |
+|----------|---------------|-------------|-------------------------------------------|
+| 1 -  6   | Record name   | ATOM        | or HETATM                                 |
+| 7 - 11   | Integer       | serial      | Atom  serial number.                      |
+| 13 - 16  | Atom          | name        | Atom name.                                |
+| 17       | Character     | altLoc      | Alternate location indicator.             |
+| 18 - 20  | Residue name  | resName     | Residue name.                             |
+| 22       | Character     | chainID     | Chain identifier.                         |
+| 23 - 26  | Integer       | resSeq      | Residue sequence number.                  |
+| 27       | AChar         | iCode       | Code for insertion of residues.           |
+| 31 - 38  | Real(8.3)     | x           | Orthogonal coordinates for X in Angstroms.|
+| 39 - 46  | Real(8.3)     | y           | Orthogonal coordinates for Y in Angstroms.|
+| 47 - 54  | Real(8.3)     | z           | Orthogonal coordinates for Z in Angstroms.|
+| 55 - 60  | Real(6.2)     | occupancy   | Occupancy, may be shifted by a column.    |
+| 61 - 66  | Real(6.2)     | tempFactor  | Temperature  factor, as occupancy.        |
+| 73 - 76  | LString(4)    | segID       | Segment identifier, not in the standard.  |
+| 77 - 78  | LString(2)    | element     | Element symbol, right-justified. Read     |
+|          |               |             | with usual capitalization, such as `Fe`.  |
+| 79 - 80  | LString(2)    | charge      | Charge  on the atom.                      |
+"#],
+    pub atom_record_parser<Record>,
+    do_parse!(
+        hetero: alt!(do_parse!(atom >> tag!("  ") >> (false)) | do_parse!(hetatm >> (true)))
+            >> serial: fivedigit_integer
+            >> take!(1)
+            >> name: take_str!(4)
+            >> alt_loc: optional_char
+            >> residue_name: take_str!(3)
+            >> take!(1)
+            >> chain_id: anychar
+            >> residue_seq: residue_seq_parser
+            >> insertion_code: optional_char
+            >> take!(3)
+            >> x: call!(real(8))
+            >> y: call!(real(8))
+            >> z: call!(real(8))
+            >> values: map_opt!(peek!(till_line_ending), occupancy_temp_factor)
+            >> rest: till_line_ending
+            >> line_ending
+            >> (Record::Atom(Atom {
+                serial,
+                name: name.trim().to_owned(),
+                name_field: name.to_owned(),
+                alt_loc,
+                residue_name: residue_name.trim().to_owned(),
+                chain_id,
+                residue_seq,
+                insertion_code,
+                x,
+                y,
+                z,
+                occupancy: values.0,
+                temp_factor: values.1,
+                segment_id: column_field(rest, 18, 22),
+                element: column_field(rest, 22, 24)
+                    .map(normalize_element)
+                    .unwrap_or_default(),
+                charge: column_field(rest, 24, 26),
+                hetero,
+                anisou: None,
+            }))
+    )
+);
+
+named!(
+    anisou_line_parser<(u32, [i32; 6])>,
+    do_parse!(
+        anisou
+            >> serial: fivedigit_integer
+            >> take!(17)
+            >> u11: call!(signed_integer(7))
+            >> u22: call!(signed_integer(7))
+            >> u33: call!(signed_integer(7))
+            >> u12: call!(signed_integer(7))
+            >> u13: call!(signed_integer(7))
+            >> u23: call!(signed_integer(7))
+            >> till_line_ending
+            >> line_ending
+            >> ((serial, [u11, u22, u33, u12, u13, u23]))
+    )
+);
+
+named!(
+    sigma_line_parser<u32>,
+    do_parse!(
+        alt!(sigatm | siguij)
+            >> serial: fivedigit_integer
+            >> till_line_ending
+            >> line_ending
+            >> (serial)
+    )
+);
+
+/// Parses an ATOM or HETATM record together with the SIGATM, ANISOU and SIGUIJ records
+/// immediately following it. Anisotropic temperature factors are kept in `anisou` of
+/// the [Atom](../ast/types/struct.Atom.html), standard deviations are skipped. Records
+/// following the atom are only taken when their serial matches the atom serial.
+pub fn atom_with_anisou_parser(s: &[u8]) -> IResult<&[u8], Record> {
+    let (rest, record) = atom_record_parser(s)?;
+    let mut atom = match record {
+        Record::Atom(a) => a,
+        other => return Ok((rest, other)),
+    };
+    let serial = atom.serial;
+    let (rest, _) = opt(verify(sigma_line_parser, |s: &u32| *s == serial))(rest)?;
+    let (rest, anisou) = opt(verify(anisou_line_parser, |(s, _): &(u32, [i32; 6])| {
+        *s == serial
+    }))(rest)?;
+    let (rest, _) = opt(verify(sigma_line_parser, |s: &u32| *s == serial))(rest)?;
+    atom.anisou = anisou.map(|(_, u)| u);
+    Ok((rest, Record::Atom(atom)))
+}
+
+impl Atom {
+    /// Residue the atom belongs to
+    pub fn residue_id(&self) -> ResidueId {
+        ResidueId {
+            chain_id: self.chain_id,
+            residue_seq: self.residue_seq,
+            insertion_code: self.insertion_code,
+        }
+    }
+
+    /// Element part of the atom name, columns 13-14 of the record. Tells apart names
+    /// such as ` CA ` for an alpha carbon and `CA  ` for calcium.
+    pub fn element_hint(&self) -> &str {
+        self.name_field.get(..2).unwrap_or(&self.name_field).trim()
+    }
+
+    /// Formats the atom as an 80 column ATOM or HETATM record, without line ending.
+    /// Atom name keeps the alignment it was read with, names built by hand are
+    /// aligned by the usual convention of starting single letter elements at column 14.
+    pub fn to_pdb_line(&self) -> String {
+        let name = if self.name_field.len() == 4 && self.name_field.trim() == self.name {
+            self.name_field.clone()
+        } else if self.name.len() < 4 && self.element.trim().len() < 2 {
+            format!(" {:<3}", self.name)
+        } else {
+            format!("{:<4}", self.name)
+        };
+        format!(
+            "{:<6}{:>5} {}{}{:>3} {}{:>4}{}   {:>8.3}{:>8.3}{:>8.3}{:>6.2}{:>6.2}      {:<4}{:>2}{:<2}",
+            if self.hetero { "HETATM" } else { "ATOM" },
+            self.serial,
+            name,
+            self.alt_loc.unwrap_or(' '),
+            self.residue_name,
+            self.chain_id,
+            self.residue_seq,
+            self.insertion_code.unwrap_or(' '),
+            self.x,
+            self.y,
+            self.z,
+            self.occupancy,
+            self.temp_factor,
+            self.segment_id.as_deref().unwrap_or(""),
+            self.element.to_uppercase(),
+            self.charge.as_deref().unwrap_or(""),
+        )
+    }
+
+    /// Distance to `other` in Angstroms
+    pub fn distance(&self, other: &Atom) -> f64 {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2) + (self.z - other.z).powi(2))
+            .sqrt()
+    }
+
+    /// Moves the atom by given offsets in Angstroms
+    pub fn translate(&mut self, dx: f64, dy: f64, dz: f64) {
+        self.x += dx;
+        self.y += dy;
+        self.z += dz;
+    }
+
+    /// Multiplies coordinates of the atom with rotation matrix `m` given in row major order
+    pub fn apply_matrix(&mut self, m: &[[f64; 3]; 3]) {
+        let (x, y, z) = (self.x, self.y, self.z);
+        self.x = m[0][0] * x + m[0][1] * y + m[0][2] * z;
+        self.y = m[1][0] * x + m[1][1] * y + m[1][2] * z;
+        self.z = m[2][0] * x + m[2][1] * y + m[2][2] * z;
+    }
+}
+
+/// Computes the axis aligned bounding box enclosing all `atoms`. Result is given as
+/// `(min_x, min_y, min_z, max_x, max_y, max_z)`, `None` is returned for an empty slice.
+pub fn coordinate_bounds(atoms: &[Atom]) -> Option<(f64, f64, f64, f64, f64, f64)> {
+    let first = atoms.first()?;
+    Some(atoms.iter().skip(1).fold(
+        (first.x, first.y, first.z, first.x, first.y, first.z),
+        |(min_x, min_y, min_z, max_x, max_y, max_z), a| {
+            (
+                min_x.min(a.x),
+                min_y.min(a.y),
+                min_z.min(a.z),
+                max_x.max(a.x),
+                max_y.max(a.y),
+                max_z.max(a.z),
+            )
+        },
+    ))
+}
+
+/// Returns atoms having a negative occupancy or a negative temperature factor. Such
+/// values parse fine but usually are artifacts of refinement
+pub fn suspicious_atoms(atoms: &[Atom]) -> Vec<&Atom> {
+    atoms
+        .iter()
+        .filter(|a| a.occupancy < 0.0 || a.temp_factor < 0.0)
+        .collect()
+}
+
+/// Rewrites serials of `atoms` to `1..=n` keeping their order, closing gaps left by
+/// filtering atoms such as waters out
+pub fn renumber_serials(atoms: &mut [Atom]) {
+    for (i, a) in atoms.iter_mut().enumerate() {
+        a.serial = i as u32 + 1;
+    }
+}
+
+/// Renumbers `atoms` as [renumber_serials](fn.renumber_serials.html) does and rewrites
+/// CONECT records to refer to the new serials. Records of atoms no longer present are
+/// dropped, as are bonds to them.
+pub fn renumber_serials_with_conect(atoms: &mut [Atom], conects: &mut Vec<Conect>) {
+    let new_serials = atoms
+        .iter()
+        .enumerate()
+        .map(|(i, a)| (a.serial, i as u32 + 1))
+        .collect::<HashMap<_, _>>();
+    renumber_serials(atoms);
+    conects.retain_mut(|c| match new_serials.get(&c.serial) {
+        Some(serial) => {
+            c.serial = *serial;
+            c.bonded = c
+                .bonded
+                .iter()
+                .filter_map(|b| new_serials.get(b).copied())
+                .collect();
+            true
+        }
+        None => false,
+    });
+}
+
+/// Computes mean temperature factor of each residue, a common measure of flexibility.
+/// Residues are listed in the order they first appear in `atoms`.
+pub fn residue_bfactors(atoms: &[Atom]) -> Vec<(ResidueId, f64)> {
+    let mut index = HashMap::new();
+    let mut sums: Vec<(ResidueId, f64, u32)> = Vec::new();
+    for a in atoms {
+        let id = a.residue_id();
+        let i = *index.entry(id).or_insert_with(|| {
+            sums.push((id, 0.0, 0));
+            sums.len() - 1
+        });
+        sums[i].1 += a.temp_factor;
+        sums[i].2 += 1;
+    }
+    sums.into_iter()
+        .filter(|(_, _, count)| *count > 0)
+        .map(|(id, sum, count)| (id, sum / f64::from(count)))
+        .collect()
+}
+
+/// Detects breaks within chains, where sequence numbers of consecutive residues are not
+/// consecutive. Inserted residues such as `52A` share the number of the residue they follow
+/// and do not make a gap. Residues flanking each gap are returned, usually marking missing loops.
+pub fn chain_gaps(atoms: &[Atom]) -> Vec<(ResidueId, ResidueId)> {
+    let mut residues = atoms.iter().map(Atom::residue_id).collect::<Vec<_>>();
+    residues.dedup();
+    residues
+        .windows(2)
+        .filter(|pair| {
+            pair[0].chain_id == pair[1].chain_id && pair[1].residue_seq > pair[0].residue_seq + 1
+        })
+        .map(|pair| (pair[0], pair[1]))
+        .collect()
+}
+
+/// Reduces `atoms` to a single conformer. Among atoms of the same residue sharing an atom
+/// name but having different altLoc indicators, only the one with the highest occupancy is
+/// kept, ties going to the earlier altLoc letter. Order of the kept atoms is preserved.
+pub fn select_best_altloc(atoms: Vec<Atom>) -> Vec<Atom> {
+    let mut best: HashMap<_, usize> = HashMap::new();
+    for (i, a) in atoms.iter().enumerate() {
+        let key = (a.chain_id, a.residue_seq, a.insertion_code, a.name.as_str());
+        let current = best.entry(key).or_insert(i);
+        let b = &atoms[*current];
+        if a.occupancy > b.occupancy || (a.occupancy == b.occupancy && a.alt_loc < b.alt_loc) {
+            *current = i;
+        }
+    }
+    let mut keep = best.into_values().collect::<Vec<_>>();
+    keep.sort_unstable();
+    let mut keep = keep.into_iter().peekable();
+    atoms
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| keep.next_if_eq(i).is_some())
+        .map(|(_, a)| a)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_atom(line: &str) -> Atom {
+        match atom_record_parser(line.as_bytes()) {
+            Ok((_, Record::Atom(a))) => a,
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn atom() {
+        let res = parse_atom(
+            "ATOM      2  CA  ALA A   1      48.661  22.866  29.347  1.00 27.31           C  \n",
+        );
+        assert_eq!(res.serial, 2);
+        assert_eq!(res.name, "CA");
+        assert_eq!(res.residue_name, "ALA");
+        assert_eq!(res.chain_id, 'A');
+        assert_eq!(res.residue_seq, 1);
+        assert_eq!(res.y, 22.866);
+        assert_eq!(res.temp_factor, 27.31);
+        assert_eq!(res.element, "C");
+        assert!(!res.hetero);
+    }
+
+    #[test]
+    fn equality() {
+        let line =
+            "ATOM      1  N   MET A   1      47.699  22.521  30.384  1.00 37.82           N\n";
+        assert_eq!(parse_atom(line), parse_atom(line));
+        assert_ne!(
+            parse_atom(line),
+            parse_atom(
+                "ATOM      1  N   MET A   1      47.699  22.521  30.385  1.00 37.82           N\n"
+            )
+        );
+    }
+
+    #[test]
+    fn negative_residue_seq() {
+        let res = parse_atom(
+            "ATOM      1  N   HIS A  -3      12.403  31.072  17.215  1.00 45.10           N  \n",
+        );
+        assert_eq!(res.residue_seq, -3);
+        assert_eq!(res.residue_id().residue_seq, -3);
+    }
+
+    #[test]
+    fn hetatm() {
+        let res = parse_atom(
+            "HETATM 4786  C1  MAN A 328      29.997  27.879  -0.523  1.00 13.16           C  \n",
+        );
+        assert_eq!(res.residue_name, "MAN");
+        assert_eq!(res.z, -0.523);
+        assert!(res.hetero);
+    }
+
+    #[test]
+    fn two_letter_element() {
+        let line =
+            "HETATM 1234 FE   HEM A 142      17.120  36.051  12.580  1.00 20.14          FE  ";
+        let iron = parse_atom(&format!("{}\n", line));
+        assert_eq!(iron.element, "Fe");
+        assert_eq!(iron.to_pdb_line(), line);
+    }
+
+    #[test]
+    fn name_alignment() {
+        let carbon = parse_atom(
+            "ATOM      2  CA  ALA A   1      48.661  22.866  29.347  1.00 27.31           C  \n",
+        );
+        let calcium = parse_atom(
+            "HETATM 4790 CA    CA A 401      17.120  36.051  12.580  1.00 20.14          CA  \n",
+        );
+        assert_eq!(carbon.name, calcium.name);
+        assert_eq!(carbon.name_field, " CA ");
+        assert_eq!(calcium.name_field, "CA  ");
+        assert_eq!(carbon.element_hint(), "C");
+        assert_eq!(calcium.element_hint(), "CA");
+    }
+
+    #[test]
+    fn anisou() {
+        let (rest, res) = atom_with_anisou_parser(
+            r#"ATOM      1  N   ALA A   1      47.699  22.521  30.384  1.00 37.82           N
+ANISOU    1  N   ALA A   1     4436   5001   4927   -218    161   -442       N
+ATOM      2  CA  ALA A   1      48.661  22.866  29.347  1.00 27.31           C
+ANISOU    1  N   ALA A   1     4436   5001   4927   -218    161   -442       N
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+        match res {
+            Record::Atom(a) => {
+                assert_eq!(a.serial, 1);
+                assert_eq!(a.anisou, Some([4436, 5001, 4927, -218, 161, -442]));
+            }
+            e => panic!("{:?}", e),
+        }
+
+        let (_, res) = atom_with_anisou_parser(rest).unwrap();
+        match res {
+            Record::Atom(a) => assert_eq!(a.anisou, None),
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn shifted_occupancy() {
+        for line in &[
+            "ATOM      1  N   ALA A   1      47.699  22.521  30.384   1.00 37.82          N\n",
+            "ATOM      1  N   ALA A   1      47.699  22.521  30.384   1.00100.00          N\n",
+            "ATOM      1  N   ALA A   1      47.699  22.521  30.384  1.0  37.82           N\n",
+        ] {
+            match atom_record_parser(line.as_bytes()) {
+                Ok((_, Record::Atom(a))) => {
+                    assert_eq!(a.occupancy, 1.0);
+                    assert!(a.temp_factor == 37.82 || a.temp_factor == 100.0);
+                    assert_eq!(a.element, "N");
+                }
+                e => panic!("{:?}", e),
+            }
+        }
+        assert!(atom_record_parser(
+            "ATOM      1  N   ALA A   1      47.699  22.521  30.384      1.00 37.82       N\n"
+                .as_bytes()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn segment_id() {
+        let line =
+            "ATOM      1  N   MET A   1      47.699  22.521  30.384  1.00 37.82      PROA N  ";
+        let atom = parse_atom(&format!("{}\n", line));
+        assert_eq!(atom.segment_id, Some("PROA".to_owned()));
+        assert_eq!(atom.element, "N");
+        assert_eq!(atom.to_pdb_line(), line);
+        let atom = parse_atom(
+            "ATOM      1  N   MET A   1      47.699  22.521  30.384  1.00 37.82           N\n",
+        );
+        assert_eq!(atom.segment_id, None);
+    }
+
+    #[test]
+    fn pdb_line() {
+        for line in &[
+            "ATOM      2  CA  ALA A   1      48.661  22.866  29.347  1.00 27.31           C  ",
+            "HETATM 4790 CA    CA A 401      17.120  36.051 -12.580  0.50 20.14          CA2+",
+            "ATOM    145 HD21 ASN A  17B     -3.420 -10.501   8.027  1.00  0.00           H  ",
+        ] {
+            let atom = parse_atom(&format!("{}\n", line));
+            assert_eq!(&atom.to_pdb_line(), line);
+        }
+
+        let atom = Atom {
+            serial: 7,
+            name: "N".to_owned(),
+            residue_name: "GLY".to_owned(),
+            chain_id: 'B',
+            residue_seq: 12,
+            occupancy: 1.0,
+            element: "N".to_owned(),
+            ..Default::default()
+        };
+        assert_eq!(
+            atom.to_pdb_line(),
+            "ATOM      7  N   GLY B  12       0.000   0.000   0.000  1.00  0.00           N  "
+        );
+    }
+
+    #[test]
+    fn translate() {
+        let mut res = parse_atom(
+            "ATOM      2  CA  ALA A   1      48.661  22.866  29.347  1.00 27.31           C  \n",
+        );
+        res.translate(1.0, -2.0, 0.5);
+        assert!((res.x - 49.661).abs() < 1e-9);
+        assert!((res.y - 20.866).abs() < 1e-9);
+        assert!((res.z - 29.847).abs() < 1e-9);
+
+        res.apply_matrix(&[[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]]);
+        assert!((res.x + 20.866).abs() < 1e-9);
+        assert!((res.y - 49.661).abs() < 1e-9);
+    }
+
+    #[test]
+    fn bounds() {
+        let atoms = [
+            "ATOM      1  N   ALA A   1      47.699  22.521  30.384  1.00 37.82           N  \n",
+            "ATOM      2  CA  ALA A   1      48.661  22.866  29.347  1.00 27.31           C  \n",
+            "ATOM      3  C   ALA A   1      48.041  23.492  28.087  1.00 17.36           C  \n",
+        ]
+        .iter()
+        .map(|l| parse_atom(l))
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            coordinate_bounds(&atoms),
+            Some((47.699, 22.521, 28.087, 48.661, 23.492, 30.384))
+        );
+        assert_eq!(coordinate_bounds(&[]), None);
+    }
+
+    #[test]
+    fn renumber() {
+        let mut atoms = [
+            "ATOM      1  N   ALA A   1      47.699  22.521  30.384  1.00 37.82           N  \n",
+            "ATOM      2  CA  ALA A   1      48.661  22.866  29.347  1.00 27.31           C  \n",
+            "ATOM      3  C   ALA A   1      48.041  23.492  28.087  1.00 17.36           C  \n",
+        ]
+        .iter()
+        .map(|l| parse_atom(l))
+        .collect::<Vec<_>>();
+        atoms.remove(1);
+        let mut conects = vec![
+            Conect {
+                serial: 1,
+                bonded: vec![2, 3],
+            },
+            Conect {
+                serial: 2,
+                bonded: vec![1],
+            },
+        ];
+
+        renumber_serials_with_conect(&mut atoms, &mut conects);
+        assert_eq!(atoms.iter().map(|a| a.serial).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(atoms[1].name, "C");
+        assert_eq!(conects.len(), 1);
+        assert_eq!(conects[0].bonded, vec![2]);
+    }
+
+    #[test]
+    fn bfactors() {
+        let atoms = [
+            "ATOM      1  N   ALA A   1      47.699  22.521  30.384  1.00 30.00           N  \n",
+            "ATOM      2  CA  ALA A   1      48.661  22.866  29.347  1.00 20.00           C  \n",
+            "ATOM      3  N   GLY A   2      48.041  23.492  28.087  1.00 15.50           N  \n",
+        ]
+        .iter()
+        .map(|l| parse_atom(l))
+        .collect::<Vec<_>>();
+
+        let res = residue_bfactors(&atoms);
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].0.residue_seq, 1);
+        assert_eq!(res[0].1, 25.0);
+        assert_eq!(res[1].1, 15.5);
+        assert!(residue_bfactors(&[]).is_empty());
+    }
+
+    #[test]
+    fn gaps() {
+        let atoms = [
+            "ATOM      1  CA  ALA A  10      47.699  22.521  30.384  1.00 37.82           C  \n",
+            "ATOM      2  CA  GLY A  11      48.661  22.866  29.347  1.00 27.31           C  \n",
+            "ATOM      3  CA  SER A  11A     48.041  23.492  28.087  1.00 17.36           C  \n",
+            "ATOM      4  CA  LEU A  15      49.041  24.492  27.087  1.00 17.36           C  \n",
+            "ATOM      5  CA  LEU B  20      50.041  25.492  26.087  1.00 17.36           C  \n",
+        ]
+        .iter()
+        .map(|l| parse_atom(l))
+        .collect::<Vec<_>>();
+
+        let res = chain_gaps(&atoms);
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].0.residue_seq, 11);
+        assert_eq!(res[0].0.insertion_code, Some('A'));
+        assert_eq!(res[0].1.residue_seq, 15);
+    }
+
+    #[test]
+    fn best_altloc() {
+        let atoms = [
+            "ATOM      1  N   SER A  12      47.699  22.521  30.384  1.00 37.82           N  \n",
+            "ATOM      2  OG ASER A  12      48.661  22.866  29.347  0.40 27.31           O  \n",
+            "ATOM      3  OG BSER A  12      48.041  23.492  28.087  0.60 17.36           O  \n",
+        ]
+        .iter()
+        .map(|l| parse_atom(l))
+        .collect::<Vec<_>>();
+
+        let res = select_best_altloc(atoms);
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].serial, 1);
+        assert_eq!(res[1].alt_loc, Some('B'));
+        assert_eq!(res[1].occupancy, 0.6);
+    }
+
+    #[test]
+    fn negative_temp_factor() {
+        let atoms = [
+            "ATOM      1  N   ALA A   1      47.699  22.521  30.384  1.00 37.82           N  \n",
+            "ATOM      2  CA  ALA A   1      48.661  22.866  29.347  1.00 -2.31           C  \n",
+        ]
+        .iter()
+        .map(|l| parse_atom(l))
+        .collect::<Vec<_>>();
+
+        assert_eq!(atoms[1].temp_factor, -2.31);
+        let suspicious = suspicious_atoms(&atoms);
+        assert_eq!(suspicious.len(), 1);
+        assert_eq!(suspicious[0].serial, 2);
+    }
+}
@@ -9,7 +9,7 @@ use nom::{
         complete::{line_ending, space0, space1},
         is_alphanumeric, is_space,
     },
-    do_parse, fold_many1, map, map_res,
+    do_parse, map_opt, map_res,
     multi::separated_list,
     named, opt, Err, IResult,
 };
@@ -31,8 +31,9 @@ named!(
             >> rest: till_line_ending
             >> line_ending
             >> (Continuation::<AuthorLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
-                remaining: String::from_str(str::from_utf8(rest).unwrap()).unwrap(),
+                continuation: cont.unwrap_or_default(),
+                remaining: String::from_utf8_lossy(rest).into_owned(),
+                line_number: None,
                 phantom: PhantomData,
             })
     )
@@ -82,9 +83,9 @@ Record structure :
 
 "#],
     pub author_record_parser<Record>,
-    map!(author_line_folder, |v: Vec<u8>| {
+    map_opt!(author_line_folder, |v: Vec<u8>| {
         author_list_parser(v.as_slice())
             .map(|res| Record::Authors(Authors { authors: res.1 }))
-            .expect("Can not parse author record")
+            .ok()
     })
 );
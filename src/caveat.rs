@@ -5,11 +5,11 @@ CAVEAT warns of errors and unresolved issues in the entry. Use caution when usin
 use super::{ast::types::*, primitive::*};
 use nom::{
     character::complete::{line_ending, space0, space1},
-    do_parse, fold_many1, map, named, opt, take,
+    do_parse, map, named, opt, take,
 };
 
 use crate::make_line_folder;
-use std::{marker::PhantomData, str, str::FromStr};
+use std::marker::PhantomData;
 
 #[allow(dead_code)]
 struct CaveatLine;
@@ -24,8 +24,9 @@ named!(
             >> rest: till_line_ending
             >> line_ending
             >> (Continuation::<CaveatLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
-                remaining: String::from_str(str::from_utf8(rest).unwrap()).unwrap(),
+                continuation: cont.unwrap_or_default(),
+                remaining: String::from_utf8_lossy(rest).into_owned(),
+                line_number: None,
                 phantom: PhantomData,
             })
     )
@@ -37,7 +38,7 @@ named!(
     caveat_parser<Record>,
     do_parse!(
         space0
-            >> id_code: alphanum_word
+            >> id_code: idcode_parser
             >> space0
             >> comment: alphanum_word_with_spaces_inside
             >> space0
@@ -67,3 +68,22 @@ Record structure :
         }
     })
 );
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn caveat() {
+        match caveat_record_parser(
+            "CAVEAT     1ABC    INCORRECT CHIRALITY AT RESIDUE A 23                          \n"
+                .as_bytes(),
+        ) {
+            Ok((_, Record::Caveat(res))) => {
+                assert_eq!(res.id_code.as_str(), "1ABC");
+                assert_eq!(res.comment, "INCORRECT CHIRALITY AT RESIDUE A 23");
+            }
+            e => panic!("{:?}", e),
+        }
+    }
+}
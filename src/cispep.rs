@@ -0,0 +1,114 @@
+/*!
+Contains parsers related to [CISPEP](http://www.wwpdb.org/documentation/file-format-content/format33/sect6.html#CISPEP)
+records. CISPEP records specify the prolines and other peptides found to be in the cis conformation.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{
+    call,
+    character::complete::{anychar, line_ending},
+    do_parse, named, take, take_str,
+};
+
+named!(
+    #[doc=r#"Parses a single CISPEP record. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing a [CisPep](../ast/types/struct.CisPep.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE    | FIELD       | DEFINITION                                |
+|----------|---------------|-------------|-------------------------------------------|
+| 1 -  6   | Record name   | CISPEP      |                                           |
+| 8 - 10   | Integer       | serNum      | Record serial number.                     |
+| 12 - 14  | LString(3)    | pep1        | Residue name.                             |
+| 16       | Character     | chainID1    | Chain identifier.                         |
+| 18 - 21  | Integer       | seqNum1     | Residue sequence number.                  |
+| 22       | AChar         | icode1      | Insertion code.                           |
+| 26 - 28  | LString(3)    | pep2        | Residue name.                             |
+| 30       | Character     | chainID2    | Chain identifier.                         |
+| 32 - 35  | Integer       | seqNum2     | Residue sequence number.                  |
+| 36       | AChar         | icode2      | Insertion code.                           |
+| 44 - 46  | Integer       | modNum      | Identifies the specific model.            |
+| 54 - 59  | Real(6.2)     | measure     | Angle measurement in degrees.             |
+"#],
+    pub cispep_record_parser<Record>,
+    do_parse!(
+        cispep
+            >> take!(1)
+            >> serial: threedigit_integer
+            >> take!(1)
+            >> residue_name_1: take_str!(3)
+            >> take!(1)
+            >> chain_id_1: anychar
+            >> take!(1)
+            >> residue_seq_1: residue_seq_parser
+            >> insertion_code_1: optional_char
+            >> take!(3)
+            >> residue_name_2: take_str!(3)
+            >> take!(1)
+            >> chain_id_2: anychar
+            >> take!(1)
+            >> residue_seq_2: residue_seq_parser
+            >> insertion_code_2: optional_char
+            >> take!(7)
+            >> model_number: threedigit_integer
+            >> take!(7)
+            >> measure: call!(real(6))
+            >> till_line_ending
+            >> line_ending
+            >> (Record::CisPep(CisPep {
+                serial,
+                residue_name_1: residue_name_1.trim().to_owned(),
+                chain_id_1,
+                residue_seq_1,
+                insertion_code_1,
+                residue_name_2: residue_name_2.trim().to_owned(),
+                chain_id_2,
+                residue_seq_2,
+                insertion_code_2,
+                model_number,
+                measure,
+            }))
+    )
+);
+
+impl CisPep {
+    /// Classifies the peptide bond from the measured omega angle, bonds with
+    /// `|omega| < 90` are cis. Guards against records labeling trans bonds as cis.
+    pub fn is_cis(&self) -> bool {
+        self.measure.abs() < 90.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_cispep(line: &str) -> CisPep {
+        match cispep_record_parser(line.as_bytes()) {
+            Ok((_, Record::CisPep(c))) => c,
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn cispep() {
+        let res = parse_cispep(
+            "CISPEP   1 SER A   58    GLY A   59          0        20.91                     \n",
+        );
+        assert_eq!(res.serial, 1);
+        assert_eq!(res.residue_name_1, "SER");
+        assert_eq!(res.residue_seq_2, 59);
+        assert_eq!(res.model_number, 0);
+        assert_eq!(res.measure, 20.91);
+        assert!(res.is_cis());
+    }
+
+    #[test]
+    fn trans() {
+        let res = parse_cispep(
+            "CISPEP   2 GLY B  116    GLY B  117          0       179.62                     \n",
+        );
+        assert_eq!(res.measure, 179.62);
+        assert!(!res.is_cis());
+    }
+}
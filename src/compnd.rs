@@ -6,16 +6,19 @@ other records such as SOURCE records
 use super::{ast::types::*, primitive::*};
 use nom::{
     alt,
-    bytes::complete::tag,
+    bytes::complete::{take_till, take_while1},
+    call,
     character::complete::{line_ending, space0, space1},
-    do_parse, fold_many1, map,
-    multi::separated_list,
-    named, opt, IResult,
+    combinator::complete,
+    do_parse, map, map_opt, named, opt, tag, IResult,
 };
 
-use crate::{make_line_folder, make_token_parser};
+use crate::{
+    make_line_folder, make_token_parser,
+    writer::{continued_record_lines, token_texts},
+};
 
-use std::{marker::PhantomData, str, str::FromStr};
+use std::{marker::PhantomData, str};
 
 #[allow(dead_code)]
 struct CmpndLine;
@@ -98,16 +101,28 @@ make_token_parser!(
     r#"Parses tokens of the form "OTHER_DETAILS: PLANT ENDOCHITINASE/LYSOZYME". Returns [Token::OtherDetails](../ast/types/enum.Token.html)"#,
     other_details_parser,
     other_details,
-    alphanum_word_with_spaces_inside,
+    free_text_parser,
     a,
     Token::OtherDetails(a)
 );
 
+named!(
+    synthetic_value_parser<Synthetic>,
+    map!(
+        alphanum_word_with_spaces_inside,
+        |s: String| match s.as_str() {
+            "YES" => Synthetic::Flag(true),
+            "NO" => Synthetic::Flag(false),
+            _ => Synthetic::Description(s),
+        }
+    )
+);
+
 make_token_parser!(
-    r#"Parses tokens of the form "SYNTHETIC: XYZ". Returns [Token::Synthetic](../ast/types/enum.Token.html)"#,
+    r#"Parses tokens of the form "SYNTHETIC: YES" or "SYNTHETIC: XYZ". Returns [Token::Synthetic](../ast/types/enum.Token.html)"#,
     synthetic_parser,
     synthetic,
-    alphanum_word_with_spaces_inside,
+    synthetic_value_parser,
     a,
     Token::Synthetic(a)
 );
@@ -143,7 +158,7 @@ make_token_parser!(
     r#"Parses tokens of the form "STRAIN:  SCHMIDT-RUPPIN B". Returns [Token::Strain](../ast/types/enum.Token.html)"#,
     strain_parser,
     strain,
-    alphanum_word_with_spaces_inside,
+    word_with_punctuation,
     a,
     Token::Strain(a)
 );
@@ -152,7 +167,7 @@ make_token_parser!(
     r#"Parses tokens of the form "VARIANT: XYZ". Returns [Token::Variant](../ast/types/enum.Token.html)"#,
     variant_parser,
     variant,
-    alphanum_word_with_spaces_inside,
+    word_with_punctuation,
     a,
     Token::Variant(a)
 );
@@ -170,7 +185,7 @@ make_token_parser!(
     r#"Parses tokens of the form "ATCC: XYZ". Returns [Token::Atcc](../ast/types/enum.Token.html)"#,
     atcc_parser,
     atcc,
-    integer_with_spaces,
+    padded_integer,
     a,
     Token::Atcc(a)
 );
@@ -268,17 +283,66 @@ make_token_parser!(
 make_token_parser!(
     r#"Parses tokens of the form "EXPRESSION_SYSTEM_TAXID: 1234". Returns [Token::ExpressionSystemTaxId](../ast/types/enum.Token.html)"#,
     expression_system_tax_id_parser,
-    expression_system_tax_id,
+    expression_system_taxid,
     integer_list,
     a,
     Token::ExpressionSystemTaxId { id: a }
 );
 
+/// Upper bound for plausible NCBI taxonomy ids. Assigned ids are currently
+/// in the low millions, this leaves plenty of headroom.
+pub const MAX_TAX_ID: u32 = 10_000_000;
+
+/// Validates ids of ORGANISM_TAXID and EXPRESSION_SYSTEM_TAXID tokens. Returns a
+/// [ParseWarning](../ast/types/enum.ParseWarning.html) for each id outside of `1..=MAX_TAX_ID`
+pub fn validate_tax_ids(ids: &[u32]) -> Vec<ParseWarning> {
+    ids.iter()
+        .filter(|id| !(1..=MAX_TAX_ID).contains(*id))
+        .map(|id| ParseWarning::ImplausibleTaxId(*id))
+        .collect()
+}
+
+impl Cmpnd {
+    /// Formats the record as COMPND lines, without line endings. Each token starts
+    /// a new line, values too long for a line are wrapped at spaces onto continuation lines.
+    pub fn to_pdb_lines(&self) -> Vec<String> {
+        continued_record_lines("COMPND", token_texts(&self.tokens))
+    }
+}
+
+/// Collects chain identifiers of all CHAIN tokens of a COMPND record, sorted and deduplicated
+pub fn all_chains(cmpnd: &Cmpnd) -> Vec<String> {
+    let mut chains = cmpnd
+        .iter()
+        .filter_map(|t| match t {
+            Token::Chain { identifiers } => Some(identifiers),
+            _ => None,
+        })
+        .flatten()
+        .cloned()
+        .collect::<Vec<_>>();
+    chains.sort();
+    chains.dedup();
+    chains
+}
+
+/// Extracts residue ranges such as `1-100` from the value of a FRAGMENT token, for
+/// example `RESIDUES 1-100, 200-250`. Words which are not ranges are skipped.
+pub fn parse_fragment_ranges(s: &str) -> Vec<(i32, i32)> {
+    s.split(|c: char| c == ',' || c.is_whitespace())
+        .filter_map(|word| {
+            // a leading `-` belongs to a negative start of the range
+            let split = word.get(1..)?.find('-')? + 1;
+            Some((word[..split].parse().ok()?, word[split + 1..].parse().ok()?))
+        })
+        .collect()
+}
+
 make_token_parser!(
     r#"Parses tokens of the form "EXPRESSION_SYSTEM_STRAIN: B171". Returns [Token::ExpressionSystemStrain](../ast/types/enum.Token.html)"#,
     expression_system_strain_parser,
     expression_system_strain,
-    alphanum_word_with_spaces_inside,
+    word_with_punctuation,
     a,
     Token::ExpressionSystemStrain(a)
 );
@@ -287,7 +351,7 @@ make_token_parser!(
     r#"Parses tokens of the form "EXPRESSION_SYSTEM_VARIANT: B171". Returns [Token::ExpressionSystemVariant](../ast/types/enum.Token.html)"#,
     expression_system_variant_parser,
     expression_system_variant,
-    alphanum_word_with_spaces_inside,
+    word_with_punctuation,
     a,
     Token::ExpressionSystemVariant(a)
 );
@@ -305,7 +369,7 @@ make_token_parser!(
     r#"Parses tokens of the form "EXPRESSION_SYSTEM_ATCC_NUMBER: 7777". Returns [Token::ExpressionSystemAtcc](../ast/types/enum.Token.html)"#,
     expression_system_atcc_number_parser,
     expression_system_atcc_number,
-    integer_with_spaces,
+    padded_integer,
     a,
     Token::ExpressionSystemAtcc(a)
 );
@@ -391,6 +455,23 @@ make_token_parser!(
     Token::ExpressionSystemGene(a)
 );
 
+named!(
+    #[doc="Parses tokens with keys not defined by the format, such as \"BIOLOGICAL_UNIT: HEXAMER\". Returns [Token::Unknown](../ast/types/enum.Token.html)"],
+    unknown_token_parser<Token>,
+    do_parse!(
+        space0
+            >> key: call!(take_while1(|c: u8| c.is_ascii_uppercase() || c.is_ascii_digit() || c == b'_'))
+            >> tag!(":")
+            >> value: call!(take_till(|c| c == b';'))
+            >> (Token::Unknown {
+                key: String::from_utf8_lossy(key).into_owned(),
+                value: String::from_utf8_lossy(value).trim().to_owned(),
+            })
+    )
+);
+
+// token keys are anchored by their trailing colon, see make_token_tagger,
+// hence order of the alternatives below does not affect the result
 named!(
     token_parser<Token>,
     alt!(
@@ -435,12 +516,14 @@ named!(
             | expression_system_vector_parser
             | expression_system_plasmid_parser
             | expression_system_gene_parser
+            | unknown_token_parser
     )
 );
 
-/// parses a list of ; seperated tokens
+/// parses a list of ; seperated tokens. The last token may or may not be followed
+/// by a `;`, running out of input after a separator ends the list instead of failing
 pub fn tokens_parser(s: &[u8]) -> IResult<&[u8], Vec<Token>> {
-    separated_list(tag(";"), token_parser)(s)
+    semicolon_separated(complete(token_parser))(s)
 }
 
 named!(
@@ -453,8 +536,9 @@ named!(
             >> rest: till_line_ending
             >> line_ending
             >> (Continuation::<CmpndLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
-                remaining: String::from_str(str::from_utf8(rest).unwrap()).unwrap(),
+                continuation: cont.unwrap_or_default(),
+                remaining: String::from_utf8_lossy(rest).into_owned(),
+                line_number: None,
                 phantom: PhantomData,
             })
     )
@@ -476,11 +560,11 @@ Record layout is given below :
 "#],
 
     pub cmpnd_token_parser<Record>,
-    map!(
+    map_opt!(
         cmpnd_line_folder,
         |v: Vec<u8>|  tokens_parser(v.as_slice())
                         .map(|res| Record::Cmpnd(Cmpnd{ tokens : res.1}))
-                        .expect("Could not parse tokens")
+                        .ok()
     )
 );
 
@@ -488,6 +572,32 @@ Record layout is given below :
 mod test {
     use super::*;
 
+    #[test]
+    fn unknown_token() {
+        match cmpnd_token_parser(
+            r#"COMPND    MOL_ID: 1;
+COMPND   2 CHAIN: A;
+COMPND   3 BIOLOGICAL_UNIT: HEXAMER
+"#
+            .as_bytes(),
+        ) {
+            Ok((_, Record::Cmpnd(res))) => assert_eq!(
+                res.tokens,
+                vec![
+                    Token::MoleculeId(1),
+                    Token::Chain {
+                        identifiers: vec!["A".to_owned()]
+                    },
+                    Token::Unknown {
+                        key: "BIOLOGICAL_UNIT".to_owned(),
+                        value: "HEXAMER".to_owned()
+                    }
+                ]
+            ),
+            e => panic!("{:?}", e),
+        }
+    }
+
     #[test]
     fn mol_id_parser() {
         if let Ok((_, Token::MoleculeId(res))) = super::mol_id_parser("MOL_ID:  1".as_bytes()) {
@@ -538,6 +648,177 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_token_order() {
+        let expected = vec![
+            Token::MoleculeId(1),
+            Token::Molecule("HEMOGLOBIN ALPHA CHAIN".to_string()),
+        ];
+        let (_, res) =
+            tokens_parser("MOL_ID: 1; MOLECULE: HEMOGLOBIN ALPHA CHAIN".as_bytes()).unwrap();
+        assert_eq!(res, expected);
+
+        let (_, res) =
+            tokens_parser("MOLECULE: HEMOGLOBIN ALPHA CHAIN; MOL_ID: 1".as_bytes()).unwrap();
+        assert_eq!(res, expected.into_iter().rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_iterate_tokens() {
+        if let Ok((_, Record::Cmpnd(res))) = cmpnd_token_parser(
+            "COMPND    MOL_ID: 1;\nCOMPND   2 MOLECULE: HEMOGLOBIN ALPHA CHAIN;\nCOMPND   3 CHAIN: A\n"
+                .as_bytes(),
+        ) {
+            let mut count = 0;
+            for token in &res {
+                assert!(!token.value_string().is_empty());
+                count += 1;
+            }
+            assert_eq!(count, 3);
+            assert_eq!(res.iter().count(), 3);
+            assert_eq!(res.into_iter().next(), Some(Token::MoleculeId(1)));
+        } else {
+            panic!();
+        }
+    }
+
+    #[test]
+    fn test_all_chains() {
+        if let Ok((_, Record::Cmpnd(res))) = cmpnd_token_parser(
+            r#"COMPND    MOL_ID: 1;
+COMPND   2 MOLECULE: HEMOGLOBIN ALPHA CHAIN;
+COMPND   3 CHAIN: C, A;
+COMPND   4 MOL_ID: 2;
+COMPND   5 MOLECULE: HEMOGLOBIN BETA CHAIN;
+COMPND   6 CHAIN: D, B, A
+"#
+            .as_bytes(),
+        ) {
+            assert_eq!(all_chains(&res), vec!["A", "B", "C", "D"]);
+        } else {
+            panic!();
+        }
+    }
+
+    #[test]
+    fn test_synthetic_parser() {
+        assert_eq!(
+            synthetic_parser("SYNTHETIC: YES".as_bytes()),
+            Ok((&b""[..], Token::Synthetic(Synthetic::Flag(true))))
+        );
+        assert_eq!(
+            synthetic_parser("SYNTHETIC: CHEMICALLY SYNTHESIZED".as_bytes()),
+            Ok((
+                &b""[..],
+                Token::Synthetic(Synthetic::Description("CHEMICALLY SYNTHESIZED".to_owned()))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_strain_with_punctuation() {
+        assert_eq!(
+            strain_parser("STRAIN: SCHMIDT-RUPPIN B;".as_bytes()),
+            Ok((";".as_bytes(), Token::Strain("SCHMIDT-RUPPIN B".to_owned())))
+        );
+        assert_eq!(
+            variant_parser("VARIANT: PV.1-A".as_bytes()),
+            Ok((&b""[..], Token::Variant("PV.1-A".to_owned())))
+        );
+    }
+
+    #[test]
+    fn test_fragment_ranges() {
+        assert_eq!(
+            parse_fragment_ranges("RESIDUES 1-100, 200-250"),
+            vec![(1, 100), (200, 250)]
+        );
+        assert_eq!(parse_fragment_ranges("-5-20"), vec![(-5, 20)]);
+        assert!(parse_fragment_ranges("N-TERMINAL DOMAIN").is_empty());
+    }
+
+    #[test]
+    fn test_line_numbers() {
+        let (_, lines) = continuation_lines(cmpnd_line_parser)(
+            "COMPND    MOL_ID: 1;\nCOMPND   2 MOLECULE: HEMOGLOBIN ALPHA CHAIN\n".as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(
+            lines.iter().map(|l| l.line_number).collect::<Vec<_>>(),
+            vec![Some(1), Some(2)]
+        );
+        assert_eq!(lines[1].continuation, 2);
+    }
+
+    #[test]
+    fn test_trailing_semicolon() {
+        let (_, with) =
+            tokens_parser("MOL_ID: 1; MOLECULE: HEMOGLOBIN; MUTATION: NO;".as_bytes()).unwrap();
+        let (_, without) =
+            tokens_parser("MOL_ID: 1; MOLECULE: HEMOGLOBIN; MUTATION: NO".as_bytes()).unwrap();
+        assert_eq!(with, without);
+        assert_eq!(with.len(), 3);
+
+        if let Ok((_, Record::Cmpnd(res))) = cmpnd_token_parser(
+            "COMPND    MOL_ID: 1;\nCOMPND   2 MOLECULE: HEMOGLOBIN ALPHA CHAIN;\n".as_bytes(),
+        ) {
+            assert_eq!(res.tokens.len(), 2);
+        } else {
+            panic!();
+        }
+    }
+
+    #[test]
+    fn test_token_alternative_order() {
+        use nom::branch::alt;
+        for input in &["MOL_ID: 1", "MOLECULE: HEMOGLOBIN"] {
+            let (_, first) =
+                alt((super::mol_id_parser, super::molecule_parser))(input.as_bytes()).unwrap();
+            let (_, second) =
+                alt((super::molecule_parser, super::mol_id_parser))(input.as_bytes()).unwrap();
+            assert_eq!(first, second);
+        }
+    }
+
+    #[test]
+    fn test_tax_id_validation() {
+        if let Ok((_, Token::ExpressionSystemTaxId { id })) =
+            expression_system_tax_id_parser("EXPRESSION_SYSTEM_TAXID: 562".as_bytes())
+        {
+            assert!(validate_tax_ids(&id).is_empty());
+        } else {
+            panic!();
+        }
+
+        if let Ok((_, Token::ExpressionSystemTaxId { id })) =
+            expression_system_tax_id_parser("EXPRESSION_SYSTEM_TAXID: 0".as_bytes())
+        {
+            assert_eq!(
+                validate_tax_ids(&id),
+                vec![ParseWarning::ImplausibleTaxId(0)]
+            );
+        } else {
+            panic!();
+        }
+    }
+
+    #[test]
+    fn test_other_details_parser() {
+        let (_, res) = tokens_parser(
+            "OTHER_DETAILS: PLANT ENDOCHITINASE/LYSOZYME, MUTANT; CHAIN: A".as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            vec![
+                Token::OtherDetails("PLANT ENDOCHITINASE/LYSOZYME, MUTANT".to_owned()),
+                Token::Chain {
+                    identifiers: vec!["A".to_owned()]
+                }
+            ]
+        );
+    }
+
     #[test]
     fn test_cmpnd_parser() {
         if let Ok((_, res)) = cmpnd_line_folder(
@@ -0,0 +1,193 @@
+/*!
+Contains parsers related to [CONECT](http://www.wwpdb.org/documentation/file-format-content/format33/sect10.html#CONECT)
+records. CONECT records specify connectivity between atoms for which coordinates are supplied.
+*/
+use super::{
+    ast::{pdb_file::PdbFile, types::*},
+    primitive::*,
+    ssbond::sulfur_atom,
+};
+use nom::{character::complete::line_ending, do_parse, named};
+
+use std::{collections::BTreeSet, fmt::Write, str};
+
+named!(
+    #[doc=r#"Parses a single CONECT record. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing a [Conect](../ast/types/struct.Conect.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE    | FIELD       | DEFINITION                                |
+|----------|---------------|-------------|-------------------------------------------|
+| 1 -  6   | Record name   | CONECT      |                                           |
+| 7 - 11   | Integer       | serial      | Atom  serial number                       |
+| 12 - 16  | Integer       | serial      | Serial number of bonded atom              |
+| 17 - 21  | Integer       | serial      | Serial number of bonded atom              |
+| 22 - 26  | Integer       | serial      | Serial number of bonded atom              |
+| 27 - 31  | Integer       | serial      | Serial number of bonded atom              |
+"#],
+    pub conect_record_parser<Record>,
+    do_parse!(
+        conect
+            >> serial: fivedigit_integer
+            >> rest: till_line_ending
+            >> line_ending
+            >> (Record::Conect(Conect {
+                serial,
+                bonded: rest
+                    .chunks(5)
+                    .take(4)
+                    .filter_map(|field| str::from_utf8(field).ok())
+                    .filter_map(|field| field.trim().parse().ok())
+                    .collect(),
+            }))
+    )
+);
+
+/// Longest distance in Angstroms still considered a covalent bond. Covers disulfide
+/// bonds of about 2.05 and leaves room for coordinate error.
+pub const COVALENT_BOND_MAX: f64 = 2.3;
+
+/// Classifies each bond of `conect`, in the order of its bonded atoms, from the
+/// distance of the atoms in `atoms`.
+pub fn classify_conect(conect: &Conect, atoms: &[Atom]) -> Vec<BondKind> {
+    let find = |serial: u32| atoms.iter().find(|a| a.serial == serial);
+    conect
+        .bonded
+        .iter()
+        .map(|bonded| match (find(conect.serial), find(*bonded)) {
+            (Some(a), Some(b)) if a.distance(b) <= COVALENT_BOND_MAX => BondKind::Covalent,
+            (Some(_), Some(_)) => BondKind::LongRange,
+            _ => BondKind::Unresolved,
+        })
+        .collect()
+}
+
+/// Exports bonds of CONECT and SSBOND records as an undirected Graphviz DOT graph. Atoms
+/// of the first model are the nodes, labeled with their name and residue. Disulfide bonds
+/// join the SG atoms of their cysteines, residues without coordinates are used as nodes
+/// when these are missing.
+pub fn to_dot(pdb: &PdbFile<Vec<Record>>) -> String {
+    let atoms = pdb.first_model_atoms().collect::<Vec<_>>();
+    let sulfur = |chain_id: char, residue_seq: i32, insertion_code: Option<char>| {
+        let residue = ResidueId {
+            chain_id,
+            residue_seq,
+            insertion_code,
+        };
+        sulfur_atom(atoms.iter().copied(), &residue).map_or_else(
+            || {
+                format!(
+                    "\"{} {}{}\"",
+                    chain_id,
+                    residue_seq,
+                    insertion_code.unwrap_or_default()
+                )
+            },
+            |a| a.serial.to_string(),
+        )
+    };
+    let mut bonds = BTreeSet::new();
+    let mut disulfides = Vec::new();
+    for record in pdb.records() {
+        match record {
+            Record::Conect(c) => bonds.extend(
+                c.bonded
+                    .iter()
+                    .map(|b| (c.serial.min(*b), c.serial.max(*b))),
+            ),
+            Record::SsBond(s) => disulfides.push((
+                sulfur(s.chain_id_1, s.residue_seq_1, s.insertion_code_1),
+                sulfur(s.chain_id_2, s.residue_seq_2, s.insertion_code_2),
+            )),
+            _ => {}
+        }
+    }
+    let mut dot = String::from("graph connectivity {\n");
+    let nodes = bonds
+        .iter()
+        .flat_map(|(a, b)| vec![*a, *b])
+        .chain(
+            disulfides
+                .iter()
+                .flat_map(|(a, b)| vec![a, b])
+                .filter_map(|n| n.parse().ok()),
+        )
+        .collect::<BTreeSet<u32>>();
+    for atom in atoms.iter().filter(|a| nodes.contains(&a.serial)) {
+        let _ = writeln!(
+            dot,
+            "    {} [label=\"{} {} {}{}\"];",
+            atom.serial, atom.name, atom.residue_name, atom.chain_id, atom.residue_seq
+        );
+    }
+    for (a, b) in &bonds {
+        let _ = writeln!(dot, "    {} -- {};", a, b);
+    }
+    for (a, b) in &disulfides {
+        let _ = writeln!(dot, "    {} -- {} [label=\"SSBOND\"];", a, b);
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bond_kinds() {
+        let atom = |serial, x| Atom {
+            serial,
+            x,
+            ..Default::default()
+        };
+        let atoms = vec![atom(1, 0.0), atom(2, 1.52), atom(3, 2.9)];
+        let conect = Conect {
+            serial: 1,
+            bonded: vec![2, 3, 4],
+        };
+        assert_eq!(
+            classify_conect(&conect, &atoms),
+            vec![
+                BondKind::Covalent,
+                BondKind::LongRange,
+                BondKind::Unresolved
+            ]
+        );
+    }
+
+    #[test]
+    fn dot() {
+        let (_, pdb) = crate::parse(
+            r#"SSBOND   1 CYS A    6    CYS A  127                          1555   1555  2.03  
+ATOM     48  SG  CYS A   6      10.510  20.000  15.000  1.00 10.00           S
+ATOM    980  SG  CYS A 127      12.540  20.000  15.000  1.00 10.00           S
+HETATM 1001  C1  NAG A 201      20.000  20.000  15.000  1.00 10.00           C
+HETATM 1002  C2  NAG A 201      21.500  20.000  15.000  1.00 10.00           C
+CONECT 1001 1002
+CONECT 1002 1001
+"#,
+        )
+        .unwrap();
+        let dot = to_dot(&pdb);
+        assert!(dot.starts_with("graph connectivity {"));
+        assert!(dot.contains("48 -- 980 [label=\"SSBOND\"];"));
+        assert!(dot.contains("48 [label=\"SG CYS A6\"];"));
+        assert_eq!(dot.matches("1001 -- 1002;").count(), 1);
+    }
+
+    #[test]
+    fn conect() {
+        match conect_record_parser(
+            "CONECT 1179  746 1184 1195 1203                                                 \n"
+                .as_bytes(),
+        ) {
+            Ok((_, Record::Conect(res))) => {
+                assert_eq!(res.serial, 1179);
+                assert_eq!(res.bonded, vec![746, 1184, 1195, 1203]);
+            }
+            e => panic!("{:?}", e),
+        }
+    }
+}
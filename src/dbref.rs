@@ -9,17 +9,17 @@ named!(
     do_parse!(
         dbref
         >> space1
-        >> idcode : idcode_parser_len
+        >> idcode : pdb_idcode_parser_len
         >> space1
         >> chain_id : anychar
         >> tag!(" ")
-        >> seq_begin : fourdigit_integer
-        >> initial_sequence : opt!(anychar)
+        >> seq_begin : residue_seq_parser
+        >> initial_sequence : optional_char
         >> tag!(" ")
-        >> seq_end : fourdigit_integer
-        >> ending_sequence : opt!(anychar)
+        >> seq_end : residue_seq_parser
+        >> ending_sequence : optional_char
         >> space1
-        >> database : alphanum_word
+        >> database : database_parser
         >> space1
         >> db_accession : alphanum_word
         >> space1
@@ -36,10 +36,16 @@ named!(
             Dbref{
                 idcode,
                 chain_id,
-                seq_begin,
-                initial_sequence,
-                seq_end,
-                ending_sequence,
+                begin: ResidueId {
+                    chain_id,
+                    residue_seq: seq_begin,
+                    insertion_code: initial_sequence,
+                },
+                end: ResidueId {
+                    chain_id,
+                    residue_seq: seq_end,
+                    insertion_code: ending_sequence,
+                },
                 database,
                 db_accession,
                 db_idcode,
@@ -54,7 +60,7 @@ named!(
 
 #[cfg(test)]
 mod test {
-    use super::{super::Record, dbref_record_parser};
+    use super::{super::Database, super::Record, super::ResidueId, dbref_record_parser};
     #[test]
     pub fn dbref() {
         if let Ok((_, Record::Dbref(res))) = dbref_record_parser(
@@ -63,6 +69,37 @@ mod test {
             .as_bytes(),
         ) {
             assert_eq!(res.idcode, "2JHQ");
+            assert_eq!(res.database, Database::Unp);
+            assert_eq!(res.begin.residue_seq, 1);
+            assert_eq!(res.end.insertion_code, None);
+        } else {
+            panic!();
+        }
+    }
+
+    #[test]
+    pub fn insertion_codes() {
+        if let Ok((_, Record::Dbref(res))) = dbref_record_parser(
+            r#"DBREF  1ABC H    1A  100B UNP    P01857   IGHG1_HUMAN      1    102  
+"#
+            .as_bytes(),
+        ) {
+            assert_eq!(
+                res.begin,
+                ResidueId {
+                    chain_id: 'H',
+                    residue_seq: 1,
+                    insertion_code: Some('A')
+                }
+            );
+            assert_eq!(
+                res.end,
+                ResidueId {
+                    chain_id: 'H',
+                    residue_seq: 100,
+                    insertion_code: Some('B')
+                }
+            );
         } else {
             assert!(false);
         }
@@ -1,7 +1,7 @@
 use super::{ast::types::*, primitive::*};
 use nom::{
     character::complete::{anychar, line_ending, space0, space1},
-    do_parse, named, opt, tag,
+    do_parse, named, tag,
 };
 
 named!(
@@ -9,17 +9,17 @@ named!(
     do_parse!(
         dbref1
         >> space1
-        >> idcode : idcode_parser_len
+        >> idcode : pdb_idcode_parser_len
         >> space1
         >> chain_id : anychar
         >> tag!(" ")
-        >> seq_begin : fourdigit_integer
-        >> initial_sequence : opt!(anychar)
+        >> seq_begin : residue_seq_parser
+        >> initial_sequence : optional_char
         >> tag!(" ")
-        >> seq_end : fourdigit_integer
-        >> ending_sequence : opt!(anychar)
+        >> seq_end : residue_seq_parser
+        >> ending_sequence : optional_char
         >> space1
-        >> database : alphanum_word
+        >> database : database_parser
         >> space1
         >> db_idcode : db_id_code_parser_len
         >> till_line_ending
@@ -27,10 +27,16 @@ named!(
             Dbref1{
                 idcode,
                 chain_id,
-                seq_begin,
-                initial_sequence,
-                seq_end,
-                ending_sequence,
+                begin: ResidueId {
+                    chain_id,
+                    residue_seq: seq_begin,
+                    insertion_code: initial_sequence,
+                },
+                end: ResidueId {
+                    chain_id,
+                    residue_seq: seq_end,
+                    insertion_code: ending_sequence,
+                },
                 database,
                 db_idcode
             }
@@ -43,7 +49,7 @@ named!(
     do_parse!(
         dbref2
         >> space1
-        >> idcode : idcode_parser_len
+        >> idcode : pdb_idcode_parser_len
         >> space1
         >> chain_id : anychar
         >> space1
@@ -0,0 +1,66 @@
+/*!
+Contains [ProteinError](enum.ProteinError.html), the error type of fallible entry points such as
+reading an entry from disk.
+*/
+use std::{fmt, io, str::Utf8Error};
+
+/// Errors which stop an entry from being parsed at all
+#[derive(Debug)]
+pub enum ProteinError {
+    /// file could not be opened or read
+    Io(io::Error),
+    /// input is not valid UTF-8
+    InvalidUtf8(Utf8Error),
+    /// model serials of an ensemble skip `missing`, reported in strict mode
+    ModelGap { missing: u32 },
+    /// line `line`, numbered from 1, is longer than the `max_line_length` option allows
+    LineTooLong { line: usize, length: usize },
+    /// mandatory record, named by its record name, is absent from the entry
+    MissingRecord(&'static str),
+    /// record at line `line` stops parsing at column `column`, both numbered from 1.
+    /// `column` is `None` for records whose failure can not be located
+    MalformedRecord { line: usize, column: Option<usize> },
+}
+
+impl fmt::Display for ProteinError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProteinError::Io(e) => write!(f, "Can not read pdb file: {}", e),
+            ProteinError::InvalidUtf8(e) => write!(f, "Pdb file is not valid UTF-8: {}", e),
+            ProteinError::ModelGap { missing } => write!(f, "Model {} is missing", missing),
+            ProteinError::LineTooLong { line, length } => {
+                write!(f, "Line {} is too long, {} bytes", line, length)
+            }
+            ProteinError::MissingRecord(name) => write!(f, "Mandatory {} record is missing", name),
+            ProteinError::MalformedRecord { line, column } => match column {
+                Some(column) => write!(f, "Malformed record at line {}, column {}", line, column),
+                None => write!(f, "Malformed record at line {}", line),
+            },
+        }
+    }
+}
+
+impl std::error::Error for ProteinError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ProteinError::Io(e) => Some(e),
+            ProteinError::InvalidUtf8(e) => Some(e),
+            ProteinError::ModelGap { .. }
+            | ProteinError::LineTooLong { .. }
+            | ProteinError::MissingRecord(_)
+            | ProteinError::MalformedRecord { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for ProteinError {
+    fn from(e: io::Error) -> Self {
+        ProteinError::Io(e)
+    }
+}
+
+impl From<Utf8Error> for ProteinError {
+    fn from(e: Utf8Error) -> Self {
+        ProteinError::InvalidUtf8(e)
+    }
+}
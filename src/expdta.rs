@@ -17,14 +17,15 @@ use nom::{
     alt,
     bytes::complete::tag,
     character::complete::{line_ending, space0, space1},
-    do_parse, fold_many1, map,
+    combinator::map,
+    do_parse, map_opt,
     multi::separated_list,
     named, opt, tag, IResult,
 };
 
 use crate::make_line_folder;
 
-use std::{marker::PhantomData, str, str::FromStr};
+use std::marker::PhantomData;
 
 #[allow(dead_code)]
 struct ExpdataLine;
@@ -69,13 +70,21 @@ parses single experimental technique. Returns
                 >> tag!("SOLUTION SCATTERING")
                 >> space0
                 >> (ExperimentalTechnique::SolutionScattering)
+        ) | do_parse!(
+            space0
+                >> tag!("THEORETICAL MODEL")
+                >> space0
+                >> (ExperimentalTechnique::TheoreticalModel)
         )
     )
 );
 
-/// parses ; separated list of experimental techniques
+/// parses list of experimental techniques separated either by `;` or `,`
 pub fn experimental_technique_list_parser(s: &[u8]) -> IResult<&[u8], Vec<ExperimentalTechnique>> {
-    separated_list(tag(";"), experimental_technique_parser)(s)
+    map(
+        semicolon_separated(separated_list(tag(","), experimental_technique_parser)),
+        |techniques| techniques.into_iter().flatten().collect(),
+    )(s)
 }
 named!(
     expdata_line_parser<Continuation<ExpdataLine>>,
@@ -87,8 +96,9 @@ named!(
             >> rest: till_line_ending
             >> line_ending
             >> (Continuation::<ExpdataLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
-                remaining: String::from_str(str::from_utf8(rest).unwrap()).unwrap(),
+                continuation: cont.unwrap_or_default(),
+                remaining: String::from_utf8_lossy(rest).into_owned(),
+                line_number: None,
                 phantom: PhantomData,
             })
     )
@@ -111,9 +121,166 @@ Record structure:
 |         |                              | optional comment desc                     |
 "#],
     pub expdata_record_parser<Record>,
-    map!(expdata_line_folder, |v: Vec<u8>| {
+    map_opt!(expdata_line_folder, |v: Vec<u8>| {
         experimental_technique_list_parser(v.as_slice())
             .map(|res| Record::Experimental(Experimental { techniques: res.1 }))
-            .expect("Can not parse expdta records")
+            .ok()
     })
 );
+
+impl Experimental {
+    /// Whether the entry was determined by an experiment, `false` only when every technique
+    /// is a theoretical model. Entries listing no technique are taken as experimental
+    pub fn is_experimental(&self) -> bool {
+        self.techniques.is_empty()
+            || !self
+                .techniques
+                .iter()
+                .all(|t| *t == ExperimentalTechnique::TheoreticalModel)
+    }
+
+    /// Whether solution or solid-state NMR is among the techniques of the entry
+    pub fn includes_nmr(&self) -> bool {
+        self.techniques.iter().any(|t| {
+            matches!(
+                t,
+                ExperimentalTechnique::SolutionNmr | ExperimentalTechnique::SolidStateNmr
+            )
+        })
+    }
+}
+
+/// Cross checks experimental techniques with the resolution given in REMARK 2. Resolution
+/// is NOT APPLICABLE for NMR, so a numeric `resolution` on an entry whose techniques are
+/// all NMR yields a [ParseWarning](../ast/types/enum.ParseWarning.html)
+pub fn check_method_resolution(
+    expdta: &Experimental,
+    resolution: Option<f64>,
+) -> Option<ParseWarning> {
+    let nmr_only = !expdta.techniques.is_empty()
+        && expdta.techniques.iter().all(|t| {
+            matches!(
+                t,
+                ExperimentalTechnique::SolutionNmr | ExperimentalTechnique::SolidStateNmr
+            )
+        });
+    resolution
+        .filter(|_| nmr_only)
+        .map(ParseWarning::NmrWithResolution)
+}
+
+/// Cross checks experimental techniques with the Z value of the CRYST1 record. Entries
+/// determined by crystallography must have at least one molecule in the unit cell, other
+/// entries list a dummy cell with Z of 1 and are not checked.
+pub fn check_method_cell(expdta: &Experimental, cryst1: &Cryst1) -> Option<ParseWarning> {
+    let crystal = expdta.techniques.iter().any(|t| {
+        matches!(
+            t,
+            ExperimentalTechnique::XRayDiffraction
+                | ExperimentalTechnique::NeutronDiffraction
+                | ExperimentalTechnique::FiberDiffraction
+                | ExperimentalTechnique::ElectronCrystallography
+        )
+    });
+    Some(ParseWarning::ZeroMoleculesPerCell).filter(|_| crystal && cryst1.molecules_per_cell() == 0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn technique_separators() {
+        let expected = vec![
+            ExperimentalTechnique::XRayDiffraction,
+            ExperimentalTechnique::NeutronDiffraction,
+        ];
+        for input in &[
+            "X-RAY DIFFRACTION; NEUTRON DIFFRACTION",
+            "X-RAY DIFFRACTION, NEUTRON DIFFRACTION",
+        ] {
+            let (_, res) = experimental_technique_list_parser(input.as_bytes()).unwrap();
+            assert_eq!(res, expected);
+        }
+    }
+
+    #[test]
+    fn electron_techniques() {
+        for (input, expected) in &[
+            (
+                "EXPDTA    ELECTRON MICROSCOPY\n",
+                ExperimentalTechnique::ElectronMicroscopy,
+            ),
+            (
+                "EXPDTA    ELECTRON CRYSTALLOGRAPHY\n",
+                ExperimentalTechnique::ElectronCrystallography,
+            ),
+        ] {
+            match expdata_record_parser(input.as_bytes()) {
+                Ok((_, Record::Experimental(res))) => {
+                    assert_eq!(res.techniques, vec![expected.clone()])
+                }
+                e => panic!("{:?}", e),
+            }
+        }
+    }
+
+    #[test]
+    fn method_resolution() {
+        let nmr = Experimental {
+            techniques: vec![ExperimentalTechnique::SolutionNmr],
+        };
+        assert_eq!(
+            check_method_resolution(&nmr, Some(2.1)),
+            Some(ParseWarning::NmrWithResolution(2.1))
+        );
+        assert_eq!(check_method_resolution(&nmr, None), None);
+
+        let xray = Experimental {
+            techniques: vec![ExperimentalTechnique::XRayDiffraction],
+        };
+        assert_eq!(check_method_resolution(&xray, Some(2.1)), None);
+    }
+
+    #[test]
+    fn method_cell() {
+        let xray = Experimental {
+            techniques: vec![ExperimentalTechnique::XRayDiffraction],
+        };
+        let nmr = Experimental {
+            techniques: vec![ExperimentalTechnique::SolutionNmr],
+        };
+        let empty = Cryst1::default();
+        assert_eq!(
+            check_method_cell(&xray, &empty),
+            Some(ParseWarning::ZeroMoleculesPerCell)
+        );
+        assert_eq!(check_method_cell(&nmr, &empty), None);
+        let cell = Cryst1 {
+            z: 4,
+            ..Default::default()
+        };
+        assert_eq!(check_method_cell(&xray, &cell), None);
+    }
+
+    #[test]
+    fn theoretical_model() {
+        let input = r#"EXPDTA    THEORETICAL MODEL
+REMARK   2
+REMARK   2 RESOLUTION.    2.10 ANGSTROMS.
+"#;
+        let (_, pdb) = crate::parse(input).unwrap();
+        match &pdb.records()[0] {
+            Record::Experimental(e) => {
+                assert_eq!(e.techniques, vec![ExperimentalTechnique::TheoreticalModel]);
+                assert!(!e.is_experimental());
+            }
+            r => panic!("{:?}", r),
+        }
+        assert_eq!(pdb.resolution(), None);
+        let (_, pdb) =
+            crate::parse(&input.replace("THEORETICAL MODEL", "X-RAY DIFFRACTION")).unwrap();
+        assert_eq!(pdb.resolution(), Some(2.1));
+        assert!(Experimental::default().is_experimental());
+    }
+}
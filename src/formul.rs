@@ -0,0 +1,121 @@
+/*!
+Contains parsers related to [FORMUL](http://www.wwpdb.org/documentation/file-format-content/format33/sect4.html#FORMUL)
+records. FORMUL records present the chemical formula and charge of a non-standard group.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{character::complete::line_ending, do_parse, many0, named, take, take_str, verify};
+
+use std::marker::PhantomData;
+
+#[allow(dead_code)]
+struct FormulText;
+
+struct FormulLine {
+    component_number: u32,
+    het_id: String,
+    water: bool,
+    text: Continuation<FormulText>,
+}
+
+named!(
+    formul_line_parser<FormulLine>,
+    do_parse!(
+        formul
+            >> take!(2)
+            >> component_number: twodigit_integer
+            >> take!(2)
+            >> het_id: take_str!(3)
+            >> take!(1)
+            >> rest: till_line_ending
+            >> line_ending
+            >> (FormulLine {
+                component_number,
+                het_id: het_id.trim().to_owned(),
+                water: text_field(rest, 2, 3) == "*",
+                text: Continuation::<FormulText> {
+                    continuation: text_field(rest, 0, 2).parse().unwrap_or_default(),
+                    remaining: text_field(rest, 3, 54).to_owned(),
+                    line_number: None,
+                    phantom: PhantomData,
+                },
+            })
+    )
+);
+
+named!(
+    #[doc=r#"Parses the FORMUL lines of a single heterogen. Formulas of large heterogens span
+continuation lines which are folded into a single formula. If successfull returns
+[Record](../ast/types/enum.Record.html) variant containing a [Formul](../ast/types/struct.Formul.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE    | FIELD       | DEFINITION                                  |
+|----------|---------------|-------------|---------------------------------------------|
+| 1 -  6   | Record name   | FORMUL      |                                             |
+| 9 - 10   | Integer       | compNum     | Component  number.                          |
+| 13 - 15  | LString(3)    | hetID       | Het identifier.                             |
+| 17 - 18  | Integer       | continuation| Continuation number.                        |
+| 19       | Character     | asterisk    | "*" for water.                              |
+| 20 - 70  | String        | text        | Chemical formula.                           |
+"#],
+    pub formul_record_parser<Record>,
+    do_parse!(
+        first: formul_line_parser
+            >> rest: many0!(verify!(formul_line_parser, |l: &FormulLine| l.het_id
+                == first.het_id))
+            >> (Record::Formul(Formul {
+                component_number: first.component_number,
+                het_id: first.het_id.clone(),
+                water: first.water,
+                formula: String::from_utf8(fold_continuations(
+                    std::iter::once(first.text)
+                        .chain(rest.into_iter().map(|l| l.text))
+                        .zip(1..)
+                        .map(|(mut text, n)| {
+                            text.line_number = Some(n);
+                            text
+                        })
+                        .collect()
+                ))
+                .unwrap_or_default(),
+            }))
+    )
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn continued_formula() {
+        match formul_record_parser(
+            r#"FORMUL   3  NAG    8(C8 H15 N O6)
+FORMUL   4  CDL    C81 H156 O17
+FORMUL   4  CDL  2 P2 2-
+FORMUL   5  HOH   *95(H2 O)
+"#
+            .as_bytes(),
+        ) {
+            Ok((rest, Record::Formul(res))) => {
+                assert_eq!(res.het_id, "NAG");
+                assert_eq!(res.formula, "8(C8 H15 N O6)");
+                assert!(rest.starts_with(b"FORMUL   4"));
+                match formul_record_parser(rest) {
+                    Ok((rest, Record::Formul(res))) => {
+                        assert_eq!(res.component_number, 4);
+                        assert_eq!(res.formula, "C81 H156 O17 P2 2-");
+                        match formul_record_parser(rest) {
+                            Ok((_, Record::Formul(res))) => {
+                                assert!(res.water);
+                                assert_eq!(res.formula, "95(H2 O)");
+                            }
+                            e => panic!("{:?}", e),
+                        }
+                    }
+                    e => panic!("{:?}", e),
+                }
+            }
+            e => panic!("{:?}", e),
+        }
+    }
+}
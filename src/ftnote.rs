@@ -0,0 +1,85 @@
+/*!
+Contains parsers related to legacy FTNOTE records. Footnotes were used by older PDB format versions to
+annotate the coordinate section and are folded into REMARK records in current files. Parsing them lets
+archival files be read without stopping at an unknown record.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{
+    character::complete::{line_ending, space0, space1},
+    do_parse, many0, named, verify,
+};
+
+#[derive(Debug, Clone)]
+struct FtnoteLine {
+    number: u32,
+    text: String,
+}
+
+named!(
+    ftnote_line_parser<FtnoteLine>,
+    do_parse!(
+        ftnote
+            >> space1
+            >> number: integer
+            >> space0
+            >> rest: till_line_ending
+            >> line_ending
+            >> (FtnoteLine {
+                number,
+                text: String::from_utf8_lossy(rest).trim_end().to_owned(),
+            })
+    )
+);
+
+named!(
+    #[doc=r#"Parses FTNOTE records. All consecutive lines carrying the same footnote number are folded
+into a single footnote. If successfull returns [Record](../ast/types/enum.Record.html) variant containing
+[FootNote](../ast/types/struct.FootNote.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD   | DEFINITION                                  |
+|----------|--------------|---------|---------------------------------------------|
+| 1 -  6   | Record name  | FTNOTE  |                                             |
+| 8 - 10   | Integer      | number  | Footnote number, repeated on continuations. |
+| 12 - 70  | String       | text    | Text of the footnote.                       |
+"#],
+    pub ftnote_record_parser<Record>,
+    do_parse!(
+        first: ftnote_line_parser
+            >> rest: many0!(verify!(ftnote_line_parser, |l: &FtnoteLine| l.number
+                == first.number))
+            >> (Record::FootNote(FootNote {
+                number: first.number,
+                text: rest.into_iter().fold(first.text, |acc, l| acc + " " + &l.text),
+            }))
+    )
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str;
+
+    #[test]
+    fn ftnote() {
+        let res = ftnote_record_parser(
+            r#"FTNOTE   1 RESIDUES PRO 8 AND PRO 17 ARE CIS PROLINES.
+FTNOTE   1 SEE REMARK 5.
+FTNOTE   2 WATER 301 IS DISORDERED.
+"#
+            .as_bytes(),
+        );
+        match res {
+            Ok((rest, Record::FootNote(note))) => {
+                assert_eq!(note.number, 1);
+                assert_eq!(
+                    note.text,
+                    "RESIDUES PRO 8 AND PRO 17 ARE CIS PROLINES. SEE REMARK 5."
+                );
+                assert!(str::from_utf8(rest).unwrap().starts_with("FTNOTE   2"));
+            }
+            e => panic!("{:?}", e),
+        }
+    }
+}
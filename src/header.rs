@@ -3,10 +3,12 @@ Contains parsers related to [Header](http://www.wwpdb.org/documentation/file-for
 records. Header record gives information about identity of this pdb file.
 */
 use super::{ast::types::*, primitive::*};
+use chrono::{Datelike, NaiveDate, Utc};
 use nom::{
     character::complete::{line_ending, multispace1, space0},
-    do_parse, map, named, take_str,
+    do_parse, map, map_res, named, take_str,
 };
+use std::str::FromStr;
 
 named!(#[doc=r#"Parses a line of [Header](http://www.wwpdb.org/documentation/file-format-content/format33/sect2.html#HEADER) record.
 This type of record is neither separated to multi lines nor repeated. There is just single line of unique header record in a pdb file.
@@ -30,13 +32,68 @@ Record structure :
             >> classification_p: map!(take_str!(40), str::trim)
             >> deposition_date_p: date_parser
             >> multispace1
-            >> id_code_p: take_str!(4)
+            >> id_code_p: map_res!(take_str!(4), IdCode::from_str)
             >> space0
             >> line_ending
             >> (Record::Header (Header{
                 classification: classification_p.to_string(),
                 deposition_date: deposition_date_p,
-                id_code: id_code_p.to_string()
+                id_code: id_code_p
             }))
     )
 );
+
+impl Header {
+    /// Four-digit year of the deposition date, `None` if the header carries the placeholder
+    /// date of [Header::default](struct.Header.html#impl-Default)
+    pub fn deposition_year(&self) -> Option<i32> {
+        Some(self.deposition_date)
+            .filter(|d| *d != Header::default().deposition_date)
+            .map(|d| d.year())
+    }
+}
+
+/// first year of entries deposited to the PDB
+pub const FIRST_DEPOSITION_YEAR: i32 = 1971;
+
+/// Validates a date of `record`. Returns a
+/// [ParseWarning](../ast/types/enum.ParseWarning.html) for dates before
+/// `FIRST_DEPOSITION_YEAR` or after today, mostly caused by a misread two-digit year
+pub fn validate_date(record: &'static str, date: NaiveDate) -> Option<ParseWarning> {
+    Some(ParseWarning::SuspiciousDate { record, date })
+        .filter(|_| date.year() < FIRST_DEPOSITION_YEAR || date > Utc::now().date_naive())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn deposition_year() {
+        match header_parser(
+            "HEADER    PHOTOSYNTHESIS                          09-SEP-09   3JUA              \n"
+                .as_bytes(),
+        ) {
+            Ok((_, Record::Header(h))) => {
+                assert_eq!(h.deposition_year(), Some(2009));
+                assert_eq!(Header::default().deposition_year(), None);
+            }
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn suspicious_dates() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(validate_date("HEADER", date(2009, 9, 9)), None);
+        assert_eq!(validate_date("HEADER", date(1971, 1, 1)), None);
+        assert_eq!(
+            validate_date("HEADER", date(1969, 12, 31)),
+            Some(ParseWarning::SuspiciousDate {
+                record: "HEADER",
+                date: date(1969, 12, 31)
+            })
+        );
+        assert!(validate_date("REVDAT", date(2069, 1, 1)).is_some());
+    }
+}
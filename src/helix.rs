@@ -0,0 +1,137 @@
+/*!
+Contains parsers related to [HELIX](http://www.wwpdb.org/documentation/file-format-content/format33/sect5.html#HELIX)
+records. HELIX records are used to identify the position of helices in the molecule.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{
+    character::complete::{anychar, line_ending},
+    do_parse, named, take, take_str,
+};
+
+named!(
+    #[doc=r#"Parses a single HELIX record. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing a [Helix](../ast/types/struct.Helix.html) instance. Trailing class, comment and
+length columns may be left out.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE    | FIELD       | DEFINITION                                  |
+|----------|---------------|-------------|---------------------------------------------|
+| 1 -  6   | Record name   | HELIX       |                                             |
+| 8 - 10   | Integer       | serNum      | Serial number of the helix.                 |
+| 12 - 14  | LString(3)    | helixID     | Helix identifier.                           |
+| 16 - 18  | Residue name  | initResName | Name of the initial residue.                |
+| 20       | Character     | initChainID | Chain identifier for the initial residue.   |
+| 22 - 25  | Integer       | initSeqNum  | Sequence number of the initial residue.     |
+| 26       | AChar         | initICode   | Insertion code of the initial residue.      |
+| 28 - 30  | Residue  name | endResName  | Name of the terminal residue of the helix.  |
+| 32       | Character     | endChainID  | Chain identifier for the terminal residue.  |
+| 34 - 37  | Integer       | endSeqNum   | Sequence number of the terminal residue.    |
+| 38       | AChar         | endICode    | Insertion code of the terminal residue.     |
+| 39 - 40  | Integer       | helixClass  | Helix class (see below).                    |
+| 41 - 70  | String        | comment     | Comment about this helix.                   |
+| 72 - 76  | Integer       | length      | Length of this helix.                       |
+"#],
+    pub helix_record_parser<Record>,
+    do_parse!(
+        helix
+            >> take!(2)
+            >> serial: threedigit_integer
+            >> take!(1)
+            >> helix_id: take_str!(3)
+            >> take!(1)
+            >> init_residue_name: take_str!(3)
+            >> take!(1)
+            >> init_chain_id: anychar
+            >> take!(1)
+            >> init_residue_seq: residue_seq_parser
+            >> init_insertion_code: optional_char
+            >> take!(1)
+            >> end_residue_name: take_str!(3)
+            >> take!(1)
+            >> end_chain_id: anychar
+            >> take!(1)
+            >> end_residue_seq: residue_seq_parser
+            >> rest: till_line_ending
+            >> line_ending
+            >> (Record::Helix(Helix {
+                serial,
+                helix_id: helix_id.trim().to_owned(),
+                init_residue_name: init_residue_name.trim().to_owned(),
+                init_chain_id,
+                init_residue_seq,
+                init_insertion_code,
+                end_residue_name: end_residue_name.trim().to_owned(),
+                end_chain_id,
+                end_residue_seq,
+                end_insertion_code: text_field(rest, 0, 1).chars().next(),
+                helix_class: text_field(rest, 1, 3).parse().ok(),
+                comment: Some(text_field(rest, 3, 33))
+                    .filter(|c| !c.is_empty())
+                    .map(String::from),
+                declared_length: text_field(rest, 34, 39).parse().ok(),
+            }))
+    )
+);
+
+impl Helix {
+    /// Number of residues of the helix computed from its initial and terminal residues
+    pub fn length(&self) -> i32 {
+        self.end_residue_seq - self.init_residue_seq + 1
+    }
+}
+
+/// Compares the length column of `helix` with the length computed from its residue range
+pub fn check_length(helix: &Helix) -> Option<ParseWarning> {
+    helix
+        .declared_length
+        .filter(|declared| *declared != helix.length())
+        .map(|declared| ParseWarning::HelixLengthMismatch {
+            serial: helix.serial,
+            declared,
+            computed: helix.length(),
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_helix(line: &str) -> Helix {
+        match helix_record_parser(line.as_bytes()) {
+            Ok((_, Record::Helix(h))) => h,
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn helix() {
+        let res = parse_helix(
+            "HELIX    1  HA GLY A   86  GLY A   94  1                                   9    \n",
+        );
+        assert_eq!(res.serial, 1);
+        assert_eq!(res.helix_id, "HA");
+        assert_eq!(res.init_residue_seq, 86);
+        assert_eq!(res.end_chain_id, 'A');
+        assert_eq!(res.helix_class, Some(1));
+        assert_eq!(res.declared_length, Some(9));
+        assert_eq!(res.length(), 9);
+        assert_eq!(check_length(&res), None);
+    }
+
+    #[test]
+    fn length_mismatch() {
+        let res = parse_helix(
+            "HELIX    2  HB LYS A  114  ASN A  126  1 N-TERMINAL CAP                   12    \n",
+        );
+        assert_eq!(res.comment, Some("N-TERMINAL CAP".to_owned()));
+        assert_eq!(
+            check_length(&res),
+            Some(ParseWarning::HelixLengthMismatch {
+                serial: 2,
+                declared: 12,
+                computed: 13
+            })
+        );
+    }
+}
@@ -0,0 +1,100 @@
+/*!
+Contains parsers related to [HET](http://www.wwpdb.org/documentation/file-format-content/format33/sect4.html#HET)
+records. HET records describe non-standard residues, such as prosthetic groups, inhibitors, solvent
+molecules and ions, for which coordinates are supplied.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{
+    character::complete::{anychar, line_ending},
+    do_parse, named, tag, take, take_str,
+};
+
+use std::str;
+
+named!(
+    #[doc=r#"Parses a single HET record. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing a [Het](../ast/types/struct.Het.html) instance. Description text is trimmed,
+a blank description is `None`.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE    | FIELD       | DEFINITION                                       |
+|----------|---------------|-------------|--------------------------------------------------|
+| 1 -  6   | Record name   | HET         |                                                  |
+| 8 - 10   | LString(3)    | hetID       | Het identifier, right-justified.                 |
+| 13       | Character     | ChainID     | Chain  identifier.                               |
+| 14 - 17  | Integer       | seqNum      | Sequence  number.                                |
+| 18       | AChar         | iCode       | Insertion  code.                                 |
+| 21 - 25  | Integer       | numHetAtoms | Number of HETATM records for the group present   |
+|          |               |             | in the entry.                                    |
+| 31 - 70  | String        | text        | Text describing Het group.                       |
+"#],
+    pub het_record_parser<Record>,
+    do_parse!(
+        het >> tag!("    ")
+            >> het_id: take_str!(3)
+            >> take!(2)
+            >> chain_id: anychar
+            >> residue_seq: residue_seq_parser
+            >> insertion_code: optional_char
+            >> take!(2)
+            >> num_het_atoms: fivedigit_integer
+            >> rest: till_line_ending
+            >> line_ending
+            >> (Record::Het(Het {
+                het_id: het_id.trim().to_owned(),
+                chain_id,
+                residue_seq,
+                insertion_code,
+                num_het_atoms,
+                text: rest
+                    .get(5..45.min(rest.len()))
+                    .and_then(|text| str::from_utf8(text).ok())
+                    .map(str::trim)
+                    .filter(|text| !text.is_empty())
+                    .map(String::from),
+            }))
+    )
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_het(line: &str) -> Het {
+        match het_record_parser(line.as_bytes()) {
+            Ok((_, Record::Het(h))) => h,
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn het() {
+        let res = parse_het(
+            "HET    MAN  A 328      22                                                       \n",
+        );
+        assert_eq!(res.het_id, "MAN");
+        assert_eq!(res.chain_id, 'A');
+        assert_eq!(res.residue_seq, 328);
+        assert_eq!(res.num_het_atoms, 22);
+        assert_eq!(res.text, None);
+    }
+
+    #[test]
+    fn het_text() {
+        let res = parse_het(
+            "HET    UDP  A1457      25     PHOSPHATE GROUP                                   \n",
+        );
+        assert_eq!(res.residue_seq, 1457);
+        assert_eq!(res.text, Some("PHOSPHATE GROUP".to_owned()));
+    }
+
+    #[test]
+    fn hetatm_is_not_het() {
+        assert!(het_record_parser(
+            "HETATM 4786  C1  MAN A 328      29.997  27.879  -0.523  1.00 13.16           C  \n"
+                .as_bytes()
+        )
+        .is_err());
+    }
+}
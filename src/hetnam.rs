@@ -0,0 +1,102 @@
+/*!
+Contains parsers related to [HETNAM](http://www.wwpdb.org/documentation/file-format-content/format33/sect4.html#HETNAM)
+records. HETNAM records give the chemical name of the compound with the given het identifier.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{character::complete::line_ending, do_parse, many0, named, take, take_str, verify};
+
+struct HetnamLine {
+    het_id: String,
+    text: String,
+}
+
+named!(
+    hetnam_line_parser<HetnamLine>,
+    do_parse!(
+        hetnam
+            >> take!(5)
+            >> het_id: take_str!(3)
+            >> rest: till_line_ending
+            >> line_ending
+            >> (HetnamLine {
+                het_id: het_id.trim().to_owned(),
+                text: text_field(rest, 1, 56).to_owned(),
+            })
+    )
+);
+
+/// Joins continued parts of a chemical name. Names are broken after a hyphen or at a
+/// space, a part ending with a hyphen is continued without a space
+fn join_name<'a>(parts: impl Iterator<Item = &'a str>) -> String {
+    parts.fold(String::new(), |mut name, part| {
+        if !name.is_empty() && !name.ends_with('-') {
+            name.push(' ');
+        }
+        name.push_str(part);
+        name
+    })
+}
+
+named!(
+    #[doc=r#"Parses the HETNAM lines of a single heterogen, joining continued parts of its name.
+If successfull returns [Record](../ast/types/enum.Record.html) variant containing a
+[HetName](../ast/types/struct.HetName.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE    | FIELD       | DEFINITION                                  |
+|----------|---------------|-------------|---------------------------------------------|
+| 1 -  6   | Record name   | HETNAM      |                                             |
+| 9 - 10   | Continuation  | continuation| Allows concatenation of multiple records.   |
+| 12 - 14  | LString(3)    | hetID       | Het identifier, right-justified.            |
+| 16 - 70  | String        | text        | Chemical name.                              |
+"#],
+    pub hetnam_record_parser<Record>,
+    do_parse!(
+        first: hetnam_line_parser
+            >> rest: many0!(verify!(hetnam_line_parser, |l: &HetnamLine| l.het_id
+                == first.het_id))
+            >> (Record::HetName(HetName {
+                het_id: first.het_id.clone(),
+                name: join_name(
+                    std::iter::once(first.text.as_str()).chain(rest.iter().map(|l| l.text.as_str()))
+                ),
+            }))
+    )
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn continued_name() {
+        match hetnam_record_parser(
+            r#"HETNAM     NAG N-ACETYL-D-GLUCOSAMINE
+HETNAM     FAD FLAVIN-ADENINE
+HETNAM   2 FAD DINUCLEOTIDE
+HETNAM     BGC 2-ACETAMIDO-2-DEOXY-
+HETNAM   2 BGC BETA-D-GLUCOPYRANOSE
+"#
+            .as_bytes(),
+        ) {
+            Ok((rest, Record::HetName(res))) => {
+                assert_eq!(res.het_id, "NAG");
+                assert_eq!(res.name, "N-ACETYL-D-GLUCOSAMINE");
+                match hetnam_record_parser(rest) {
+                    Ok((rest, Record::HetName(res))) => {
+                        assert_eq!(res.name, "FLAVIN-ADENINE DINUCLEOTIDE");
+                        match hetnam_record_parser(rest) {
+                            Ok((_, Record::HetName(res))) => {
+                                assert_eq!(res.name, "2-ACETAMIDO-2-DEOXY-BETA-D-GLUCOPYRANOSE")
+                            }
+                            e => panic!("{:?}", e),
+                        }
+                    }
+                    e => panic!("{:?}", e),
+                }
+            }
+            e => panic!("{:?}", e),
+        }
+    }
+}
@@ -7,12 +7,12 @@ use super::{ast::types::*, primitive::*};
 use nom::{
     alt,
     character::complete::{line_ending, space0, space1},
-    do_parse, fold_many1, map, map_res, named, opt, tag, take_str,
+    do_parse, fold_many1, map, map_opt, map_res, named, opt, tag, take_str,
 };
 
 use crate::author::author_list_parser;
 
-use std::{marker::PhantomData, str, str::FromStr};
+use std::marker::PhantomData;
 
 use crate::make_line_folder;
 
@@ -63,8 +63,9 @@ named!(
             >> rest: till_line_ending
             >> line_ending
             >> (Continuation::<JrnlAuthorLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
-                remaining: String::from_str(str::from_utf8(rest).unwrap()).unwrap(),
+                continuation: cont.unwrap_or_default(),
+                remaining: String::from_utf8_lossy(rest).into_owned(),
+                line_number: None,
                 phantom: PhantomData,
             })
     )
@@ -93,10 +94,10 @@ named!(
 
 "#],
     pub jrnl_author_record_parser<Record>,
-    map!(jrnl_author_line_folder, |jrnl_author: Vec<u8>| {
+    map_opt!(jrnl_author_line_folder, |jrnl_author: Vec<u8>| {
         author_list_parser(jrnl_author.as_slice())
             .map(|res| Record::JournalAuthors(JournalAuthors{ authors: res.1 }))
-            .expect("Can not parse journal author record")
+            .ok()
     })
 );
 
@@ -111,8 +112,9 @@ named!(
             >> rest: till_line_ending
             >> line_ending
             >> (Continuation::<JrnlTitleLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
-                remaining: String::from_str(str::from_utf8(rest).unwrap()).unwrap(),
+                continuation: cont.unwrap_or_default(),
+                remaining: String::from_utf8_lossy(rest).into_owned(),
+                line_number: None,
                 phantom: PhantomData,
             })
     )
@@ -142,7 +144,7 @@ named!(
 "#],
     pub jrnl_title_record_parser<Record>,
     map!(jrnl_title_line_folder, |jrnl_title: Vec<u8>| {
-        Record::JournalTitle(JournalTitle{ title : String::from(str::from_utf8(jrnl_title.as_slice()).unwrap())})
+        Record::JournalTitle(JournalTitle{ title : String::from_utf8_lossy(&jrnl_title).into_owned()})
     })
 );
 
@@ -157,8 +159,9 @@ named!(
             >> rest: till_line_ending
             >> line_ending
             >> (Continuation::<JrnlEditLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
-                remaining: String::from_str(str::from_utf8(rest).unwrap()).unwrap(),
+                continuation: cont.unwrap_or_default(),
+                remaining: String::from_utf8_lossy(rest).into_owned(),
+                line_number: None,
                 phantom: PhantomData,
             })
     )
@@ -211,7 +214,7 @@ named!(
             >> space0
             >> line_ending
             >> (JrnlRefLine {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
+                continuation: cont.unwrap_or_default(),
                 publication_name: publication_name.trim().to_owned(),
                 volume,
                 page,
@@ -283,8 +286,9 @@ named!(
             >> rest: till_line_ending
             >> line_ending
             >> (Continuation::<JrnlPublLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
-                remaining: String::from_str(str::from_utf8(rest).unwrap().trim()).unwrap(),
+                continuation: cont.unwrap_or_default(),
+                remaining: String::from_utf8_lossy(rest).trim().to_owned(),
+                line_number: None,
                 phantom: PhantomData,
             })
     )
@@ -315,7 +319,7 @@ If successfull returns [Record](../ast/types/enum.Record.html) variant that cont
     "#],
     pub  jrnl_publ_record_parser<Record>,
     map!(jrnl_publ_line_folder, |jrnl_publ: Vec<u8>| {
-        Record::JournalPublication(JournalPublication{ publication : String::from(str::from_utf8(jrnl_publ.as_slice()).unwrap())})
+        Record::JournalPublication(JournalPublication{ publication : String::from_utf8_lossy(&jrnl_publ).into_owned()})
     })
 );
 
@@ -349,7 +353,7 @@ variant that contains [JournalCitation](../ast/types/struct.JournalCitation.html
             >> (
                 Record::JournalCitation(JournalCitation{
                     serial_type,
-                    serial : serial.map(|s| String::from_str(str::from_utf8(s).unwrap().trim()).unwrap())
+                    serial : serial.map(|s| String::from_utf8_lossy(s).trim().to_owned())
                 })
             )
     )
@@ -376,9 +380,7 @@ accession number of the publication related to the entry. If successfull returns
     do_parse!(
         jrnl >> space1
             >> tag!("PMID")
-            >> space1
-            >> pmid_id : integer
-            >> space0
+            >> pmid_id : padded_integer
             >> line_ending
             >> (
                 Record::JournalPubMedId(JournalPubMedId{
@@ -414,7 +416,7 @@ returns [Record](../ast/types/enum.Record.html) variant containing
             >> line_ending
             >> (
                 Record::JournalDoi(JournalDoi{
-                    id : String::from_str(str::from_utf8(id).unwrap().trim()).unwrap(),
+                    id : String::from_utf8_lossy(id).trim().to_owned(),
                 })
             )
     )
@@ -422,9 +424,21 @@ returns [Record](../ast/types/enum.Record.html) variant containing
 
 #[cfg(test)]
 mod test {
-    use super::{jrnl_refn_record_parser, jrnl_title_record_parser};
+    use super::{jrnl_pmid_record_parser, jrnl_refn_record_parser, jrnl_title_record_parser};
     use crate::ast::types::{JournalCitation, Record, SerialNumber};
 
+    #[test]
+    fn test_pmid_parser() {
+        if let Ok((_, Record::JournalPubMedId(res))) = jrnl_pmid_record_parser(
+            "JRNL        PMID   2153105                                                      \n"
+                .as_bytes(),
+        ) {
+            assert_eq!(res.id, 2153105);
+        } else {
+            panic!();
+        }
+    }
+
     #[test]
     fn test_refn_parser() {
         let res = jrnl_refn_record_parser(
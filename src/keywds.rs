@@ -5,12 +5,12 @@ The KEYWDS record contains a set of terms relevant to the entry.
 use super::{ast::types::*, primitive::*};
 use nom::{
     character::complete::{line_ending, space0, space1},
-    do_parse, fold_many1, map, named, opt,
+    do_parse, map_opt, named, opt,
 };
 
 use crate::make_line_folder;
 
-use std::{marker::PhantomData, str, str::FromStr};
+use std::{marker::PhantomData, str};
 
 #[allow(dead_code)]
 struct KeywdsLine;
@@ -25,8 +25,9 @@ named!(
             >> rest: till_line_ending
             >> line_ending
             >> (Continuation::<KeywdsLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
-                remaining: String::from_str(str::from_utf8(rest).unwrap()).unwrap(),
+                continuation: cont.unwrap_or_default(),
+                remaining: String::from_utf8_lossy(rest).into_owned(),
+                line_number: None,
                 phantom: PhantomData,
             })
     )
@@ -49,9 +50,48 @@ named!(
 
  "#],
     pub keywds_parser<Record>,
-    map!(keywds_line_folder, |v: Vec<u8>| keywds_value_parser(
+    map_opt!(keywds_line_folder, |v: Vec<u8>| keywds_value_parser(
         v.as_slice()
     )
     .map(|res| Record::Keywds (Keywds{ keywords: res.1 }))
-    .expect("Can not parse keywds record"))
+    .ok())
 );
+
+/// Returns the most significant keyword of the entry. Keywords are listed in
+/// decreasing significance, so this is the first one.
+pub fn primary_keyword(keywds: &Keywds) -> Option<&str> {
+    keywds
+        .keywords
+        .iter()
+        .map(String::as_str)
+        .find(|k| !k.is_empty())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keyword_order() {
+        if let Ok((_, Record::Keywds(res))) = keywds_parser(
+            r#"KEYWDS    LYASE, TRICARBOXYLIC ACID CYCLE, MITOCHONDRION, OXIDATIVE
+KEYWDS   2 METABOLISM
+"#
+            .as_bytes(),
+        ) {
+            assert_eq!(
+                res.keywords,
+                vec![
+                    "LYASE",
+                    "TRICARBOXYLIC ACID CYCLE",
+                    "MITOCHONDRION",
+                    "OXIDATIVE METABOLISM"
+                ]
+            );
+            assert_eq!(primary_keyword(&res), Some("LYASE"));
+        } else {
+            panic!();
+        }
+        assert_eq!(primary_keyword(&Keywds::default()), None);
+    }
+}
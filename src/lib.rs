@@ -3,33 +3,87 @@ Patoz is a strict, type safe PDB file parser. Converts text PDB file into a
 traversable record struct.
  */
 #![recursion_limit = "128"]
+#![cfg_attr(
+    test,
+    allow(clippy::assertions_on_constants, clippy::bool_assert_comparison)
+)]
 
 extern crate nom;
 
 mod ast;
+pub mod atom;
 pub mod author;
 pub mod caveat;
+pub mod cispep;
 pub mod compnd;
+pub mod conect;
 pub mod dbref;
 pub mod dbref1;
+pub mod error;
 pub mod expdta;
+pub mod formul;
+pub mod ftnote;
 pub mod header;
+pub mod helix;
+pub mod het;
+pub mod hetnam;
 pub mod jrnl;
 pub mod keywds;
+pub mod master;
 pub mod mdltyp;
+pub mod model;
 pub mod nummdl;
 pub mod obslte;
+pub mod options;
 pub mod primitive;
 mod record;
 pub mod remark;
+pub mod residue;
 pub mod revdat;
 pub mod seqadv;
 pub mod seqres;
+pub mod sheet;
+pub mod site;
 pub mod source;
 pub mod split;
 pub mod sprsde;
+pub mod ssbond;
 pub mod title;
+pub mod transform;
+pub mod validate;
+pub mod writer;
 
 pub use ast::{pdb_file::*, types::*};
+pub use error::ProteinError;
 pub use nom::IResult;
-pub use record::parse;
+pub use options::ParseOptions;
+#[cfg(feature = "mmap")]
+pub use record::parse_pdb_mmap;
+pub use record::{
+    parse, parse_bytes_with_options, parse_header_only, parse_pdb_each, parse_pdb_selective,
+    parse_with_line_counts, parse_with_options, try_parse_with_options,
+};
+
+/**
+Re-exports the most commonly used types and the parse functions, so that a single glob
+import is enough for most uses.
+
+```
+use patoz::prelude::*;
+
+let (_, pdb) = parse("KEYWDS    HYDROLASE, PHOSPHOLIPASE\n").unwrap();
+match &pdb.records()[0] {
+    Record::Keywds(k) => assert_eq!(k.keywords, vec!["HYDROLASE", "PHOSPHOLIPASE"]),
+    r => panic!("{:?}", r),
+}
+```
+*/
+pub mod prelude {
+    #[cfg(feature = "mmap")]
+    pub use crate::parse_pdb_mmap;
+    pub use crate::{
+        parse, parse_bytes_with_options, parse_header_only, parse_pdb_each, parse_pdb_selective,
+        parse_with_line_counts, parse_with_options, try_parse_with_options, Atom, ParseOptions,
+        ParseResult, PdbFile, ProteinError, Record, Token,
+    };
+}
@@ -0,0 +1,161 @@
+/*!
+Contains parsers related to [MASTER](http://www.wwpdb.org/documentation/file-format-content/format33/sect11.html#MASTER)
+records. MASTER record is a control record for bookkeeping, listing the number of lines of several
+record types in the entry.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{character::complete::line_ending, do_parse, named, take};
+
+named!(
+    #[doc=r#"Parses a MASTER record. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing a [Master](../ast/types/struct.Master.html) instance. Deprecated numTurn column
+is skipped.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE    | FIELD       | DEFINITION                                       |
+|----------|---------------|-------------|--------------------------------------------------|
+| 1 -  6   | Record name   | MASTER      |                                                  |
+| 11 - 15  | Integer       | numRemark   | Number of REMARK records                         |
+| 16 - 20  | Integer       | "0"         |                                                  |
+| 21 - 25  | Integer       | numHet      | Number of HET records                            |
+| 26 - 30  | Integer       | numHelix    | Number of HELIX records                          |
+| 31 - 35  | Integer       | numSheet    | Number of SHEET records                          |
+| 36 - 40  | Integer       | numTurn     | deprecated                                       |
+| 41 - 45  | Integer       | numSite     | Number of SITE records                           |
+| 46 - 50  | Integer       | numXform    | Number of coordinate transformation records      |
+|          |               |             | (ORIGX+SCALE+MTRIX)                              |
+| 51 - 55  | Integer       | numCoord    | Number of atomic coordinate records              |
+|          |               |             | (ATOM+HETATM)                                    |
+| 56 - 60  | Integer       | numTer      | Number of TER records                            |
+| 61 - 65  | Integer       | numConect   | Number of CONECT records                         |
+| 66 - 70  | Integer       | numSeq      | Number of SEQRES records                         |
+"#],
+    pub master_record_parser<Record>,
+    do_parse!(
+        master
+            >> take!(4)
+            >> num_remark: fivedigit_integer
+            >> take!(5)
+            >> num_het: fivedigit_integer
+            >> num_helix: fivedigit_integer
+            >> num_sheet: fivedigit_integer
+            >> take!(5)
+            >> num_site: fivedigit_integer
+            >> num_xform: fivedigit_integer
+            >> num_coord: fivedigit_integer
+            >> num_ter: fivedigit_integer
+            >> num_conect: fivedigit_integer
+            >> num_seq: fivedigit_integer
+            >> till_line_ending
+            >> line_ending
+            >> (Record::Master(Master {
+                num_remark,
+                num_het,
+                num_helix,
+                num_sheet,
+                num_site,
+                num_xform,
+                num_coord,
+                num_ter,
+                num_conect,
+                num_seq,
+            }))
+    )
+);
+
+/// Compares counts of `master` with the lines of the matching records in `records`.
+/// Returns a warning for each count which differs. REMARK and TER counts are not checked,
+/// as not every REMARK and TER line is kept as a record.
+pub fn check_master_counts(master: &Master, records: &[Record]) -> Vec<ParseWarning> {
+    let mut found = Master::default();
+    for record in records {
+        match record {
+            Record::Het(_) => found.num_het += 1,
+            Record::Helix(_) => found.num_helix += 1,
+            Record::Sheet(_) => found.num_sheet += 1,
+            Record::Site(s) => found.num_site += (s.residues.len() as u32).div_ceil(4).max(1),
+            Record::Origx(_) | Record::Scale(_) | Record::Mtrix(_) => found.num_xform += 3,
+            Record::Atom(_) => found.num_coord += 1,
+            Record::Model(m) => found.num_coord += m.atoms.len() as u32,
+            Record::Conect(_) => found.num_conect += 1,
+            Record::Seqres(s) => found.num_seq += s.serial_numbers.len() as u32,
+            _ => {}
+        }
+    }
+    [
+        ("HET", master.num_het, found.num_het),
+        ("HELIX", master.num_helix, found.num_helix),
+        ("SHEET", master.num_sheet, found.num_sheet),
+        ("SITE", master.num_site, found.num_site),
+        ("ORIGX/SCALE/MTRIX", master.num_xform, found.num_xform),
+        ("ATOM/HETATM", master.num_coord, found.num_coord),
+        ("CONECT", master.num_conect, found.num_conect),
+        ("SEQRES", master.num_seq, found.num_seq),
+    ]
+    .iter()
+    .filter(|(_, expected, found)| expected != found)
+    .map(
+        |(record, expected, found)| ParseWarning::MasterCountMismatch {
+            record,
+            expected: *expected as usize,
+            found: *found as usize,
+        },
+    )
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn master() {
+        match master_record_parser(
+            "MASTER      478    0    6   10   29    0   20    6 2977    1  139   25          \n"
+                .as_bytes(),
+        ) {
+            Ok((_, Record::Master(m))) => assert_eq!(
+                m,
+                Master {
+                    num_remark: 478,
+                    num_het: 6,
+                    num_helix: 10,
+                    num_sheet: 29,
+                    num_site: 20,
+                    num_xform: 6,
+                    num_coord: 2977,
+                    num_ter: 1,
+                    num_conect: 139,
+                    num_seq: 25,
+                }
+            ),
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn master_counts() {
+        let (_, pdb) = crate::parse(
+            r#"HET    HEM  A 142      43
+ATOM      1  N   VAL A   1      47.699  22.521  30.384  1.00 37.82           N
+HETATM    2 FE   HEM A 142      17.120  36.051  12.580  1.00 20.14          FE
+CONECT    2    1
+MASTER        0    0    1    1    0    0    0    0    2    0    1    0
+"#,
+        )
+        .unwrap();
+        let master = match pdb.records().last() {
+            Some(Record::Master(m)) => m,
+            r => panic!("{:?}", r),
+        };
+        assert_eq!(
+            check_master_counts(master, pdb.records()),
+            vec![ParseWarning::MasterCountMismatch {
+                record: "HELIX",
+                expected: 1,
+                found: 0
+            }]
+        );
+    }
+}
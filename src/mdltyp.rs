@@ -6,12 +6,12 @@ The MDLTYP record contains additional annotation pertinent to the coordinates pr
 use super::{ast::types::*, primitive::*};
 use nom::{
     character::complete::{line_ending, space0, space1},
-    do_parse, fold_many1, map, named, opt,
+    do_parse, map, named, opt,
 };
 
 use crate::make_line_folder;
 
-use std::{marker::PhantomData, str, str::FromStr};
+use std::marker::PhantomData;
 
 #[allow(dead_code)]
 struct MdltypLine;
@@ -26,8 +26,9 @@ named!(
             >> rest: till_line_ending
             >> line_ending
             >> (Continuation::<MdltypLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
-                remaining: String::from_str(str::from_utf8(rest).unwrap()).unwrap(),
+                continuation: cont.unwrap_or_default(),
+                remaining: String::from_utf8_lossy(rest).into_owned(),
+                line_number: None,
                 phantom: PhantomData,
             })
     )
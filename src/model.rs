@@ -0,0 +1,151 @@
+/*!
+Contains parsers related to the [MODEL](http://www.wwpdb.org/documentation/file-format-content/format33/sect9.html#MODEL),
+[ENDMDL](http://www.wwpdb.org/documentation/file-format-content/format33/sect9.html#ENDMDL) and
+[TER](http://www.wwpdb.org/documentation/file-format-content/format33/sect9.html#TER) records. Entries holding
+multiple structures, such as NMR ensembles, wrap the atoms of each structure between MODEL and ENDMDL records.
+X-ray entries usually list ATOM, HETATM and TER records directly, which form a single implicit model.
+*/
+use super::{ast::types::*, atom::atom_with_anisou_parser, primitive::*};
+use nom::{
+    alt,
+    character::complete::{line_ending, space0},
+    do_parse, many0, many1, map, named, opt,
+};
+use std::collections::BTreeSet;
+
+named!(
+    #[doc=r#"Parses a TER record marking the end of a chain. If successfull returns
+[Record](../ast/types/enum.Record.html) variant containing [Ter](../ast/types/struct.Ter.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE    | FIELD       | DEFINITION                                |
+|----------|---------------|-------------|-------------------------------------------|
+| 1 -  6   | Record name   | TER         |                                           |
+| 7 - 11   | Integer       | serial      | Serial number.                            |
+| 18 - 20  | Residue name  | resName     | Residue name.                             |
+| 22       | Character     | chainID     | Chain identifier.                         |
+| 23 - 26  | Integer       | resSeq      | Residue sequence number.                  |
+| 27       | AChar         | iCode       | Insertion code.                           |
+"#],
+    pub ter_record_parser<Record>,
+    do_parse!(
+        ter >> space0
+            >> serial: opt!(integer)
+            >> till_line_ending
+            >> line_ending
+            >> (Record::Ter(Ter { serial }))
+    )
+);
+
+named!(
+    atom_or_ter_parser<Option<Atom>>,
+    alt!(
+        map!(atom_with_anisou_parser, |r| match r {
+            Record::Atom(a) => Some(a),
+            _ => None,
+        }) | map!(ter_record_parser, |_| None)
+    )
+);
+
+named!(
+    atoms_parser<Vec<Atom>>,
+    map!(many0!(atom_or_ter_parser), |atoms: Vec<Option<Atom>>| atoms
+        .into_iter()
+        .flatten()
+        .collect())
+);
+
+named!(
+    #[doc=r#"Parses a MODEL record along with the ATOM, HETATM and TER records up to the matching
+ENDMDL record. If successfull returns [Record](../ast/types/enum.Record.html) variant containing
+[Model](../ast/types/struct.Model.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE    | FIELD       | DEFINITION                                |
+|----------|---------------|-------------|-------------------------------------------|
+| 1 -  6   | Record name   | MODEL       |                                           |
+| 11 - 14  | Integer       | serial      | Model serial number.                      |
+"#],
+    pub model_record_parser<Record>,
+    do_parse!(
+        model
+            >> serial: padded_integer
+            >> till_line_ending
+            >> line_ending
+            >> atoms: atoms_parser
+            >> endmdl
+            >> till_line_ending
+            >> line_ending
+            >> (Record::Model(Model { serial, atoms }))
+    )
+);
+
+named!(
+    #[doc=r#"Parses the coordinate section of an entry. Both MODEL wrapped sections of multi structure
+entries and bare ATOM/HETATM/TER records of single structure entries are accepted, the latter
+produce a single implicit [Model](../ast/types/struct.Model.html) numbered 1.
+"#],
+    pub coordinate_section_parser<Vec<Model>>,
+    alt!(
+        many1!(map!(model_record_parser, |r| match r {
+            Record::Model(m) => m,
+            _ => Model::default(),
+        })) | map!(many1!(atom_or_ter_parser), |atoms: Vec<Option<Atom>>| vec![Model {
+            serial: 1,
+            atoms: atoms.into_iter().flatten().collect(),
+        }])
+    )
+);
+
+/// Returns serials missing from the `1..=n` sequence of model `serials`, `n` being the
+/// highest serial present
+pub fn missing_model_serials<I: IntoIterator<Item = u32>>(serials: I) -> Vec<u32> {
+    let serials = serials.into_iter().collect::<BTreeSet<_>>();
+    let max = serials.iter().next_back().copied().unwrap_or(0);
+    (1..=max)
+        .filter(|serial| !serials.contains(serial))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unwrapped() {
+        let (_, models) = coordinate_section_parser(
+            r#"ATOM      1  N   ALA A   1      47.699  22.521  30.384  1.00 37.82           N
+ATOM      2  CA  ALA A   1      48.661  22.866  29.347  1.00 27.31           C
+TER       3      ALA A   1
+HETATM    4  O   HOH A 101      17.120  36.051  12.580  1.00 20.14           O
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].serial, 1);
+        assert_eq!(models[0].atoms.len(), 3);
+    }
+
+    #[test]
+    fn wrapped() {
+        let (_, models) = coordinate_section_parser(
+            r#"MODEL        1
+ATOM      1  N   ALA A   1      47.699  22.521  30.384  1.00 37.82           N
+TER       2      ALA A   1
+ENDMDL
+MODEL        2
+ATOM      1  N   ALA A   1      47.712  22.498  30.401  1.00 37.82           N
+TER
+ENDMDL
+"#
+            .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[1].serial, 2);
+        assert_eq!(models[1].atoms[0].x, 47.712);
+    }
+}
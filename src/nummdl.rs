@@ -6,7 +6,7 @@ use super::{
     ast::types::{Nummdl, Record},
     primitive::*,
 };
-use nom::{character::complete::space0, do_parse, named};
+use nom::{do_parse, named};
 
 named!(#[doc=r#"Parses sinle line of Nummdl record.
 If succesfull returns [Record](../ast/types/enum.Record.html) variant containing [Nummdl](../ast/types/struct.Nummdl.html) instance.
@@ -19,5 +19,5 @@ Record structure is given below
 | 11 - 14  | Integer     | modelNumber | Number of models. | 
 "#],
     pub nummdl_record_parser<Record>,
-    do_parse!(nummdl >> space0 >> model_number: integer >> (Record::Nummdl (Nummdl{ num: model_number })))
+    do_parse!(nummdl >> model_number: padded_integer >> (Record::Nummdl (Nummdl{ num: model_number })))
 );
@@ -5,11 +5,11 @@ records. Obslte record indicates that this entry is removed from PDB and replace
 use super::{ast::types::*, primitive::*};
 use nom::{
     character::complete::{line_ending, space0, space1},
-    do_parse, fold_many1, map, named, opt, take,
+    do_parse, map, named, opt, take,
 };
 
 use crate::make_line_folder;
-use std::{marker::PhantomData, str, str::FromStr};
+use std::{marker::PhantomData, str};
 
 #[allow(dead_code)]
 struct ObslteLine;
@@ -24,8 +24,9 @@ named!(
             >> rest: till_line_ending
             >> line_ending
             >> (Continuation::<ObslteLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
-                remaining: String::from_str(str::from_utf8(rest).unwrap()).unwrap(),
+                continuation: cont.unwrap_or_default(),
+                remaining: String::from_utf8_lossy(rest).into_owned(),
+                line_number: None,
                 phantom: PhantomData,
             })
     )
@@ -39,7 +40,7 @@ named!(
         space0
             >> cont_date: date_parser
             >> space0
-            >> ids: idcode_list
+            >> ids: pdb_idcode_list
             >> (Record::Obslte(Obslte {
                 replacement_date: cont_date,
                 replacement_ids: ids
@@ -78,7 +79,7 @@ Record structure :
         } else {
             println!("Obslte parser error");
             Record::Obslte(Obslte {
-                replacement_date: chrono::naive::MIN_DATE,
+                replacement_date: chrono::NaiveDate::MIN,
                 replacement_ids: Vec::new(),
             })
         }
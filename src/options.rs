@@ -0,0 +1,260 @@
+/*!
+Contains [ParseOptions](struct.ParseOptions.html) which alter how a pdb file is read
+by [parse_with_options](../fn.parse_with_options.html), and the input pre-passes they enable.
+*/
+use super::primitive::{real, real_sci};
+use std::{iter, ops::RangeInclusive};
+
+/// tab stop width used while expanding tab characters
+pub const TAB_WIDTH: usize = 8;
+
+/// default for the `max_line_length` option, far above the 80 columns of the format
+pub const MAX_LINE_LENGTH: usize = 4096;
+
+/// columns holding the record name in files following the format
+pub const RECORD_NAME_COLUMNS: RangeInclusive<usize> = 1..=6;
+
+/// Options for [parse_with_options](../fn.parse_with_options.html). Default options
+/// parse input as [parse](../fn.parse.html) does, except that continuation lines of COMPND,
+/// SOURCE and REVDAT records interleaved with other records are regrouped
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    /// expand tab characters to spaces at 8 column stops before parsing,
+    /// restoring fixed columns of hand edited files
+    pub expand_tabs: bool,
+    /// keep the original input lines of each record along with the line it starts at, see
+    /// [records_with_raw](../struct.PdbFile.html#method.records_with_raw)
+    pub keep_raw: bool,
+    /// replace invalid UTF-8 sequences of byte input with U+FFFD instead of stopping
+    /// at the first one, see [parse_bytes_with_options](../fn.parse_bytes_with_options.html)
+    pub lossy_utf8: bool,
+    /// make [try_parse_with_options](../fn.try_parse_with_options.html) fail on structural
+    /// problems, such as gaps in model serials, instead of reporting them as warnings
+    pub strict: bool,
+    /// 1 based columns holding the record name, for producers placing it off the
+    /// standard columns. Lines are shifted back so the record name starts at column 1
+    pub record_name_columns: RangeInclusive<usize>,
+    /// move lines of other records interleaved with the continuation lines of a COMPND,
+    /// SOURCE or REVDAT record after the record, so it is folded as a whole. Enabled by
+    /// default
+    pub regroup_continuations: bool,
+    /// longest line accepted, in bytes without the line ending. Input lacking line endings,
+    /// such as a corrupt binary file, is only parsed up to the first longer line
+    pub max_line_length: usize,
+    /// accept ATOM and HETATM coordinates written in exponent notation, such as `1.23E+01`,
+    /// as long as their value fits the `Real(8.3)` format. Without it such coordinates fail
+    /// to parse, as [real](../primitive/fn.real.html) takes plain decimal notation only
+    pub scientific_coordinates: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            expand_tabs: false,
+            keep_raw: false,
+            lossy_utf8: false,
+            strict: false,
+            record_name_columns: RECORD_NAME_COLUMNS,
+            regroup_continuations: true,
+            max_line_length: MAX_LINE_LENGTH,
+            scientific_coordinates: false,
+        }
+    }
+}
+
+/// Replaces tab characters with spaces up to the next tab stop. Columns are
+/// counted from the start of each line.
+/// # Example
+/// ```
+/// # use patoz::options::expand_tabs;
+/// assert_eq!(expand_tabs("COMPND\tMOL_ID: 1;"), "COMPND  MOL_ID: 1;");
+/// ```
+pub fn expand_tabs(s: &str) -> String {
+    let mut expanded = String::with_capacity(s.len());
+    let mut column = 0;
+    for c in s.chars() {
+        match c {
+            '\t' => {
+                let width = TAB_WIDTH - column % TAB_WIDTH;
+                expanded.extend(iter::repeat_n(' ', width));
+                column += width;
+            }
+            '\r' | '\n' => {
+                expanded.push(c);
+                column = 0;
+            }
+            _ => {
+                expanded.push(c);
+                column += 1;
+            }
+        }
+    }
+    expanded
+}
+
+/// Moves record names found in `columns` of each line to the standard columns 1-6,
+/// shifting the rest of the line along. Lines too short to hold a record name are kept.
+/// # Example
+/// ```
+/// # use patoz::options::shift_record_names;
+/// assert_eq!(shift_record_names("  KEYWDS    HYDROLASE", &(3..=8)), "KEYWDS    HYDROLASE");
+/// ```
+pub fn shift_record_names(s: &str, columns: &RangeInclusive<usize>) -> String {
+    let (start, end) = (columns.start().saturating_sub(1), *columns.end());
+    s.split_inclusive('\n')
+        .map(|line| {
+            let content = line.trim_end_matches(['\r', '\n']);
+            match (content.get(start..end), line.get(end..)) {
+                (Some(name), Some(rest)) => format!("{:<6}{}", name.trim(), rest),
+                _ => line.to_owned(),
+            }
+        })
+        .collect()
+}
+
+/// Finds the first line of `s` longer than `max` bytes. Returns its number, counted
+/// from 1, its length and the byte offset it starts at.
+pub fn find_long_line(s: &str, max: usize) -> Option<(usize, usize, usize)> {
+    let mut offset = 0;
+    for (n, line) in s.split_inclusive('\n').enumerate() {
+        let length = line.trim_end_matches(['\r', '\n']).len();
+        if length > max {
+            return Some((n + 1, length, offset));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// record name, group and continuation number of a continuation line of a record
+/// folded by the parser. REVDAT lines are grouped by their modification number
+fn continuation_of(line: &str) -> Option<(&str, &str, u32)> {
+    let field = |range: std::ops::Range<usize>| line.get(range).map(str::trim);
+    let number = |f: Option<&str>| match f {
+        Some("") | None => Some(1),
+        Some(n) => n.parse().ok(),
+    };
+    match field(0..6)? {
+        name @ ("COMPND" | "SOURCE") => Some((name, "", number(field(7..10))?)),
+        "REVDAT" => Some(("REVDAT", field(7..10)?, number(field(10..12))?)),
+        _ => None,
+    }
+}
+
+/// Moves continuation lines of COMPND, SOURCE and REVDAT records up to the line they
+/// continue, when lines of other records were interleaved between them. Interleaved lines
+/// keep their order and follow the regrouped record.
+/// # Example
+/// ```
+/// # use patoz::options::regroup_continuations;
+/// assert_eq!(
+///     regroup_continuations("COMPND    MOL_ID: 1;\nTITLE     T\nCOMPND   2 CHAIN: A\n"),
+///     "COMPND    MOL_ID: 1;\nCOMPND   2 CHAIN: A\nTITLE     T\n"
+/// );
+/// ```
+pub fn regroup_continuations(s: &str) -> String {
+    let lines = s.split_inclusive('\n').collect::<Vec<_>>();
+    regroup_order(&lines)
+        .into_iter()
+        .map(|i| lines[i])
+        .collect()
+}
+
+/// indices of `lines` in the order [regroup_continuations](fn.regroup_continuations.html)
+/// leaves them
+pub(crate) fn regroup_order(lines: &[&str]) -> Vec<usize> {
+    let mut order: Vec<usize> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let target =
+            continuation_of(line)
+                .filter(|(_, _, n)| *n > 1)
+                .and_then(|(name, group, n)| {
+                    let (j, previous) = order.iter().enumerate().rev().find_map(|(j, k)| {
+                        Some((j, continuation_of(lines[*k])?)).filter(|(_, c)| c.0 == name)
+                    })?;
+                    Some(j + 1).filter(|_| previous == (name, group, n - 1))
+                });
+        match target {
+            Some(j) => order.insert(j, i),
+            None => order.push(i),
+        }
+    }
+    order
+}
+
+/// Rewrites ATOM and HETATM coordinates given in exponent notation in the standard
+/// `Real(8.3)` format, leaving other lines and coordinates untouched. Values too large
+/// for the 8 columns of the field are left as they are, hence still fail to parse.
+/// # Example
+/// ```
+/// # use patoz::options::normalize_coordinates;
+/// assert_eq!(
+///     normalize_coordinates("ATOM      1  N   MET A   1    1.23E+01  22.521  30.384\n"),
+///     "ATOM      1  N   MET A   1      12.300  22.521  30.384\n"
+/// );
+/// ```
+pub fn normalize_coordinates(s: &str) -> String {
+    s.split_inclusive('\n')
+        .map(|line| {
+            if !(line.starts_with("ATOM  ") || line.starts_with("HETATM")) {
+                return line.to_owned();
+            }
+            let mut normalized = line.to_owned();
+            for start in [30, 38, 46] {
+                let field = match line.get(start..start + 8) {
+                    Some(field) => field.as_bytes(),
+                    None => break,
+                };
+                if real(8)(field).is_err() {
+                    if let Ok((_, value)) = real_sci(8)(field) {
+                        let text = format!("{:>8.3}", value);
+                        if text.len() == 8 {
+                            normalized.replace_range(start..start + 8, &text);
+                        }
+                    }
+                }
+            }
+            normalized
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{expand_tabs, normalize_coordinates, regroup_continuations, shift_record_names};
+
+    #[test]
+    fn tab_stops() {
+        assert_eq!(expand_tabs("\tA"), "        A");
+        assert_eq!(expand_tabs("ABC\tD\nE\tF"), "ABC     D\nE       F");
+    }
+
+    #[test]
+    fn regroup() {
+        let input = "REVDAT   1   01-JAN-20 1ABC    0\nREMARK   1\nREVDAT   1 2 JRNL\n";
+        assert_eq!(
+            regroup_continuations(input),
+            "REVDAT   1   01-JAN-20 1ABC    0\nREVDAT   1 2 JRNL\nREMARK   1\n"
+        );
+        let unrelated = "COMPND    MOL_ID: 1;\nTITLE     T\nCOMPND    MOL_ID: 2;\n";
+        assert_eq!(regroup_continuations(unrelated), unrelated);
+    }
+
+    #[test]
+    fn record_name_shift() {
+        assert_eq!(
+            shift_record_names("ATOM   1\nEND\n", &(1..=4)),
+            "ATOM     1\nEND\n"
+        );
+    }
+
+    #[test]
+    fn coordinate_normalization() {
+        assert_eq!(
+            normalize_coordinates("HETATM    1 FE   HEM A 142    -1.5E+00  2.0e1   30.384\n"),
+            "HETATM    1 FE   HEM A 142      -1.500  20.000  30.384\n"
+        );
+        let too_large = "ATOM      1  N   MET A   1    1.23E+06  22.521  30.384\n";
+        assert_eq!(normalize_coordinates(too_large), too_large);
+    }
+}
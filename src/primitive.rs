@@ -7,15 +7,19 @@ use chrono::{
 use nom::{
     alt,
     branch::alt,
-    bytes::complete::{tag, take, take_till, take_while},
+    bytes::complete::{tag, take, take_till, take_while, take_while_m_n},
     character::{
-        complete::{alpha1, alphanumeric1, digit1, multispace1, space0, space1},
+        complete::{
+            alpha1, alphanumeric1, anychar, char, digit0, digit1, line_ending, space0, space1,
+        },
         is_alphanumeric, is_digit, is_space,
     },
-    combinator::{map, map_res},
-    do_parse, fold_many0, map_res,
-    multi::separated_list,
-    named, separated_list, tag, take, take_str, IResult,
+    combinator::{map, map_opt, map_res, opt, recognize},
+    do_parse, map, map_opt, map_res,
+    multi::{many0, many1, separated_list},
+    named, separated_list,
+    sequence::{delimited, preceded, terminated, tuple},
+    tag, take, take_str, IResult,
 };
 use std::{result::Result, str, str::FromStr};
 
@@ -27,6 +31,9 @@ macro_rules! make_tagger(
         );
     );
 
+/// Generates a tagger for a CMPND/SOURCE token key. Key must be immediately followed by a
+/// colon, which anchors keys sharing a prefix (`MOL_ID`/`MOLECULE`, `CELL`/`CELL_LINE`)
+/// so a shorter key never matches the head of a longer one.
 #[macro_export]
 macro_rules! make_token_tagger(
     ($tokenname : ident) => (
@@ -61,21 +68,52 @@ macro_rules! make_token_parser(
 #[macro_export]
 macro_rules! make_line_folder (
     ($parser_name : ident, $line_parser : ident, $line_type : ty) => {
-        named!(
-            $parser_name<Vec<u8>>,
-            fold_many1!(
-                    $line_parser,
-                    Vec::new(),
-                    |acc : Vec<u8>, item : Continuation<$line_type>|{
-                        let rem = if acc.len() > 0 { " ".to_owned() + &item.remaining }else{ item.remaining };
-                        let trimmed =  rem.trim_end();
-                        acc.into_iter().chain(trimmed.bytes()).collect()
-                    }
-                )
-            );
+        fn $parser_name(s: &[u8]) -> nom::IResult<&[u8], Vec<u8>> {
+            nom::combinator::map(
+                $crate::primitive::continuation_lines::<$line_type, _>($line_parser),
+                $crate::primitive::fold_continuations,
+            )(s)
+        }
     };
 );
 
+/// Collects consecutive continuation lines of a record, numbering them from 1 in the
+/// order they were read
+pub(crate) fn continuation_lines<T, F>(
+    line_parser: F,
+) -> impl Fn(&[u8]) -> IResult<&[u8], Vec<Continuation<T>>>
+where
+    F: Fn(&[u8]) -> IResult<&[u8], Continuation<T>>,
+{
+    move |s: &[u8]| {
+        map(many1(&line_parser), |lines: Vec<Continuation<T>>| {
+            lines
+                .into_iter()
+                .zip(1..)
+                .map(|(mut line, n)| {
+                    line.line_number = Some(n);
+                    line
+                })
+                .collect()
+        })(s)
+    }
+}
+
+/// Joins remaining parts of continuation lines with a single space. Parts are trimmed,
+/// so that spaces around a join never add up, and blank parts are left out
+pub(crate) fn fold_continuations<T>(lines: Vec<Continuation<T>>) -> Vec<u8> {
+    lines.into_iter().fold(Vec::new(), |mut acc, item| {
+        let part = item.remaining.trim();
+        if !part.is_empty() {
+            if !acc.is_empty() {
+                acc.push(b' ');
+            }
+            acc.extend(part.bytes());
+        }
+        acc
+    })
+}
+
 make_tagger!(master);
 make_tagger!(header);
 make_tagger!(obslte);
@@ -102,6 +140,26 @@ make_tagger!(dbref1);
 make_tagger!(dbref2);
 make_tagger!(seqadv);
 make_tagger!(remark);
+make_tagger!(atom);
+make_tagger!(hetatm);
+make_tagger!(ftnote);
+make_tagger!(mtrix);
+make_tagger!(cispep);
+make_tagger!(het);
+make_tagger!(hetnam);
+make_tagger!(formul);
+make_tagger!(model);
+make_tagger!(endmdl);
+make_tagger!(ter);
+make_tagger!(conect);
+make_tagger!(anisou);
+make_tagger!(sigatm);
+make_tagger!(siguij);
+make_tagger!(cryst1);
+make_tagger!(helix);
+make_tagger!(sheet);
+make_tagger!(ssbond);
+make_tagger!(site);
 
 named!(
     #[doc=r#"
@@ -148,6 +206,22 @@ named!(
     map_res!(map_res!(take!(4), str::from_utf8), |s : &str| str::FromStr::from_str(s.trim()))
 );
 
+named!(
+    #[doc=r#"
+Parses the four column residue sequence number of coordinate and annotation records.
+Residues preceding the mature sequence, such as expression tags, may be numbered negative.
+# Example
+```
+# use patoz::primitive::residue_seq_parser;
+let empty_remaining  : [u8;0] = [];
+assert_eq!(Ok((&empty_remaining[..],12)), residue_seq_parser("  12".as_bytes()));
+assert_eq!(Ok((&empty_remaining[..],-3)), residue_seq_parser("  -3".as_bytes()));
+```
+    "#],
+    pub residue_seq_parser<i32>,
+    map_res!(map_res!(take!(4), str::from_utf8), |s : &str| i32::from_str(s.trim()))
+);
+
 named!(
     pub fivedigit_integer<u32>,
     map_res!(map_res!(take!(5), str::from_utf8), |s : &str| str::FromStr::from_str(s.trim()))
@@ -173,14 +247,153 @@ assert_eq!(Ok((&empty_remaining[..],1000)), integer(c.as_bytes()));
     map_res!(map_res!(digit1, str::from_utf8), str::FromStr::from_str)
 );
 
+/// Parses a fixed width real number such as the `Real(8.3)` coordinate
+/// columns of ATOM records. Surrounding spaces inside the field are ignored. Only
+/// plain decimal notation is accepted, see [real_sci](fn.real_sci.html) for exponents.
+/// Earlier versions took anything `f64::from_str` did, exponents included; such values
+/// are now rejected unless [ParseOptions::scientific_coordinates](../options/struct.ParseOptions.html#structfield.scientific_coordinates)
+/// is set.
+/// # Example
+/// ```
+/// # use patoz::primitive::real;
+/// let empty_remaining: [u8; 0] = [];
+/// assert_eq!(Ok((&empty_remaining[..], 47.699)), real(8)("  47.699".as_bytes()));
+/// assert_eq!(Ok((&empty_remaining[..], -0.523)), real(8)("  -0.523".as_bytes()));
+/// assert!(real(8)("1.23E+01".as_bytes()).is_err());
+/// ```
+pub fn real(width: usize) -> impl Fn(&[u8]) -> IResult<&[u8], f64> {
+    move |s: &[u8]| {
+        map_opt(map_res(take(width), str::from_utf8), |s: &str| {
+            Some(s.trim())
+                .filter(|s| {
+                    s.bytes()
+                        .all(|c| c.is_ascii_digit() || c == b'.' || c == b'-' || c == b'+')
+                })
+                .and_then(|s| f64::from_str(s).ok())
+        })(s)
+    }
+}
+
+/// Parses a fixed width real number as [real](fn.real.html) does, also accepting
+/// exponent notation written by some nonstandard producers
+/// # Example
+/// ```
+/// # use patoz::primitive::real_sci;
+/// let empty_remaining: [u8; 0] = [];
+/// assert_eq!(Ok((&empty_remaining[..], 12.3)), real_sci(8)("1.23E+01".as_bytes()));
+/// assert_eq!(Ok((&empty_remaining[..], 47.699)), real_sci(8)("  47.699".as_bytes()));
+/// ```
+pub fn real_sci(width: usize) -> impl Fn(&[u8]) -> IResult<&[u8], f64> {
+    move |s: &[u8]| {
+        map_opt(map_res(take(width), str::from_utf8), |s: &str| {
+            f64::from_str(s.trim()).ok().filter(|v| v.is_finite())
+        })(s)
+    }
+}
+
+/// Parses a fixed width signed integer such as the `Integer` columns of ANISOU records.
+/// Surrounding spaces inside the field are ignored.
+pub fn signed_integer(width: usize) -> impl Fn(&[u8]) -> IResult<&[u8], i32> {
+    move |s: &[u8]| {
+        map_res(map_res(take(width), str::from_utf8), |s: &str| {
+            i32::from_str(s.trim())
+        })(s)
+    }
+}
+
+/// Parses a real number with exactly `decimals` digits after the point, skipping the
+/// spaces preceding it. Unlike [real](fn.real.html) the number is not bound to fixed columns,
+/// so adjacent fields without a separating space are still told apart.
+/// # Example
+/// ```
+/// # use patoz::primitive::shifted_real;
+/// assert_eq!(Ok((&b"37.82"[..], 1.0)), shifted_real(2)("   1.0037.82".as_bytes()));
+/// ```
+pub fn shifted_real(decimals: usize) -> impl Fn(&[u8]) -> IResult<&[u8], f64> {
+    move |s: &[u8]| {
+        preceded(
+            space0,
+            map_res(
+                map_res(
+                    recognize(tuple((
+                        opt(char('-')),
+                        digit0,
+                        char('.'),
+                        take_while_m_n(decimals, decimals, is_digit),
+                    ))),
+                    str::from_utf8,
+                ),
+                f64::from_str,
+            ),
+        )(s)
+    }
+}
+
+/// rotation matrix in row major order and translation vector of a transformation
+pub type Transformation = ([[f64; 3]; 3], [f64; 3]);
+
+/// Parses the three numbered lines shared by ORIGXn, SCALEn and MTRIXn records. Each
+/// line carries a row of the matrix in columns 11-40 and an element of the translation
+/// vector in columns 46-55. Returns the matrix in row major order and the vector.
+pub fn three_line_matrix_parser(
+    tag_base: &'static str,
+) -> impl Fn(&[u8]) -> IResult<&[u8], Transformation> {
+    move |s: &[u8]| {
+        let mut matrix = [[0.0; 3]; 3];
+        let mut vector = [0.0; 3];
+        let mut input = s;
+        for (n, (row, v)) in matrix.iter_mut().zip(vector.iter_mut()).enumerate() {
+            let name = format!("{}{}", tag_base, n + 1);
+            let (rest, (_, _, m1, m2, m3, _, t, _, _)) = tuple((
+                tag(name.as_str()),
+                take(4usize),
+                real(10),
+                real(10),
+                real(10),
+                take(5usize),
+                real(10),
+                till_line_ending,
+                line_ending,
+            ))(input)?;
+            *row = [m1, m2, m3];
+            *v = t;
+            input = rest;
+        }
+        Ok((input, (matrix, vector)))
+    }
+}
+
 named!(
-    pub integer_with_spaces<u32>,
+    #[doc="Parses a single character column, mapping a blank to `None`"],
+    pub optional_char<Option<char>>,
+    map!(anychar, |c: char| if c == ' ' { None } else { Some(c) })
+);
+
+named!(
+    #[doc=r#"
+Parses a positive integer padded with spaces on either side, as right or left justified
+integer columns are. Surrounding spaces are consumed.
+# Example
+```
+# use patoz::primitive::padded_integer;
+let empty_remaining  : [u8;0] = [];
+assert_eq!(Ok((&empty_remaining[..],42)), padded_integer("  42  ".as_bytes()));
+assert_eq!(Ok((&empty_remaining[..],42)), padded_integer("42".as_bytes()));
+```
+    "#],
+    pub padded_integer<u32>,
     do_parse!(space0 >> res: integer >> space0 >> (res))
 );
 
+/// Former name of [padded_integer](fn.padded_integer.html)
+#[deprecated(note = "use padded_integer instead")]
+pub fn integer_with_spaces(s: &[u8]) -> IResult<&[u8], u32> {
+    padded_integer(s)
+}
+
 named!(
     pub integer_list<&[u8],Vec<u32>>,
-    separated_list!(tag(","), integer_with_spaces)
+    separated_list!(tag(","), padded_integer)
 );
 
 named!(
@@ -211,6 +424,25 @@ named!(
         |s : &str| {str::FromStr::from_str(s.trim())}
     )
 );
+
+named!(
+    #[doc=r#"
+Parses names holding punctuation, such as strain names. Accepts alphanumerics, spaces, hyphens and periods.
+# Example
+```
+# use patoz::primitive::word_with_punctuation;
+let empty_remaining  : [u8;0] = [];
+assert_eq!(Ok((&empty_remaining[..], "SCHMIDT-RUPPIN B".to_owned())), word_with_punctuation(" SCHMIDT-RUPPIN B".as_bytes()));
+assert_eq!(Ok((&b";"[..], "K-12".to_owned())), word_with_punctuation("K-12;".as_bytes()));
+```
+    "#],
+    pub word_with_punctuation<String>,
+    map_res!(
+        map_res!(take_while(|s| {is_alphanumeric(s) || is_space(s) || s == b'-' || s == b'.'}), str::from_utf8),
+        |s : &str| {str::FromStr::from_str(s.trim())}
+    )
+);
+
 named!(
     pub keywords_parser<String>,
     map_res!(
@@ -261,16 +493,43 @@ named!(
     )
 );
 
+/// Parses free text up to the `;` separating CMPND/SOURCE tokens, keeping any
+/// punctuation inside. Surrounding spaces are trimmed.
+/// # Example
+/// ```
+/// # use patoz::primitive::free_text_parser;
+/// let res = free_text_parser("PLANT ENDOCHITINASE/LYSOZYME; CHAIN: A".as_bytes());
+/// assert_eq!(Ok(("; CHAIN: A".as_bytes(), "PLANT ENDOCHITINASE/LYSOZYME".to_owned())), res);
+/// ```
+pub fn free_text_parser(s: &[u8]) -> IResult<&[u8], String> {
+    map_res(
+        map_res(take_till(|c: u8| c == b';'), str::from_utf8),
+        |s: &str| String::from_str(s.trim()),
+    )(s)
+}
+
 named!(
     pub month_parser<u32>,
     map_res!(ascii_word, |s: String| -> Result<u32, ()> {
         let mut parsed = Parsed::new();
         chrono::format::parse(&mut parsed, s.as_str(), StrftimeItems::new("%b"))
-            .expect("Can not parse month");
-        Result::Ok(parsed.month.unwrap())
+            .map_err(|_| ())?;
+        parsed.month.ok_or(())
     })
 );
 
+/// first two-digit year read as 19xx, entries of the PDB date back to the 1970s
+const CENTURY_PIVOT: i32 = 70;
+
+/// Expands a two-digit year of a `DD-MMM-YY` date, four-digit years are kept as they are
+fn window_year(year: i32) -> i32 {
+    match year {
+        0..=99 if year >= CENTURY_PIVOT => 1900 + year,
+        0..=99 => 2000 + year,
+        _ => year,
+    }
+}
+
 named!(
     pub date_parser<NaiveDate>,
     do_parse!(
@@ -278,8 +537,12 @@ named!(
             >> tag!("-")
             >> monthp: month_parser
             >> tag!("-")
-            >> yearp: integer
-            >> (NaiveDate::from_ymd(yearp as i32, monthp, dayp))
+            >> date: map_opt!(integer, |yearp: u32| NaiveDate::from_ymd_opt(
+                window_year(yearp as i32),
+                monthp,
+                dayp
+            ))
+            >> (date)
     )
 );
 
@@ -288,16 +551,46 @@ named!(
     do_parse!(w: alphanum_word >> space1 >> (w))
 );
 
+/// parses a space separated list of words such as the modification details of REVDAT
+/// records. Spaces following the last word, up to the padding of the line, are consumed
+/// so they never yield empty words
+pub fn idcode_list(s: &[u8]) -> IResult<&[u8], Vec<String>> {
+    many0(terminated(alphanum_word, space0))(s)
+}
+
 named!(
-    pub idcode_list<Vec<String>>,
-    fold_many0!(alphanum_word_space, Vec::new(), |mut acc: Vec<String>,
-                                                  item: String|
-     -> Vec<String> {
-        acc.push(item);
-        acc
-    })
+    #[doc=r#"
+Parses a four character pdb entry id code
+# Example
+```
+# use patoz::primitive::idcode_parser;
+assert_eq!(idcode_parser("1ABC".as_bytes()).unwrap().1, "1ABC");
+assert!(idcode_parser("ABCD".as_bytes()).is_err());
+```
+    "#],
+    pub idcode_parser<IdCode>,
+    map_res!(alphanum_word, |s: String| IdCode::from_str(&s))
 );
 
+named!(
+    #[doc=r#"
+Parses database name of DBREF records. Unknown names are kept as [Database::Other](../ast/types/enum.Database.html)
+# Example
+```
+# use patoz::{primitive::database_parser, Database};
+assert_eq!(database_parser("UNP ".as_bytes()).unwrap().1, Database::Unp);
+assert_eq!(database_parser("XYZ ".as_bytes()).unwrap().1, Database::Other("XYZ".to_owned()));
+```
+    "#],
+    pub database_parser<Database>,
+    map!(alphanum_word, |s: String| Database::from(s.as_str()))
+);
+
+/// parses space separated list of pdb entry id codes
+pub fn pdb_idcode_list(s: &[u8]) -> IResult<&[u8], Vec<IdCode>> {
+    separated_list(space1, idcode_parser)(s)
+}
+
 pub fn chain_value_parser(s: &[u8]) -> IResult<&[u8], Vec<String>> {
     separated_list(tag(","), alphanum_word_with_spaces_inside)(s)
 }
@@ -306,18 +599,29 @@ pub fn keywds_value_parser(s: &[u8]) -> IResult<&[u8], Vec<String>> {
     separated_list(tag(","), keywords_parser)(s)
 }
 
+/// Parses a list of `inner` elements separated by `;`. Spaces around each separator and
+/// around each element are skipped and the list may end with a `;`.
+pub fn semicolon_separated<'a, T, F>(inner: F) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], Vec<T>>
+where
+    F: Fn(&'a [u8]) -> IResult<&'a [u8], T>,
+{
+    move |s| {
+        terminated(
+            separated_list(tag(";"), delimited(space0, &inner, space0)),
+            opt(preceded(tag(";"), space0)),
+        )(s)
+    }
+}
+
 pub fn structural_annotation(s: &[u8]) -> IResult<&[u8], String> {
     map_res(
-        map_res(
-            take_while(|s: u8| s == b',' || is_alphanumeric(s) || is_space(s)),
-            str::from_utf8,
-        ),
-        str::FromStr::from_str,
+        take_while(|s: u8| s == b',' || is_alphanumeric(s) || is_space(s)),
+        |s| str::from_utf8(s).map(|s| s.trim().to_owned()),
     )(s)
 }
 
 pub fn structural_annotation_list_parser(s: &[u8]) -> IResult<&[u8], Vec<String>> {
-    separated_list(tag(";"), structural_annotation)(s)
+    semicolon_separated(structural_annotation)(s)
 }
 
 pub fn ec_value_parser(s: &[u8]) -> IResult<&[u8], Vec<String>> {
@@ -349,10 +653,8 @@ pub fn merge_db_ref(def_1: crate::Dbref1, def_2: Dbref2) -> Dbref {
     Dbref {
         idcode: def_1.idcode,
         chain_id: def_1.chain_id,
-        seq_begin: def_1.seq_begin,
-        initial_sequence: def_1.initial_sequence,
-        seq_end: def_1.seq_end,
-        ending_sequence: def_1.ending_sequence,
+        begin: def_1.begin,
+        end: def_1.end,
         database: def_1.database,
         db_accession: def_2.db_accession,
         db_idcode: def_1.db_idcode,
@@ -384,11 +686,12 @@ macro_rules! wrap_len(
 );
 
 wrap_len!(idcode_parser_len, String, 4u32, alphanum_word);
+wrap_len!(pdb_idcode_parser_len, IdCode, 4u32, idcode_parser);
 wrap_len!(db_id_code_parser_len, String, 13u32, db_id_code_parser);
 wrap_len!(two_space, String, 2u32, mspace);
 wrap_len!(five_space, String, 5u32, mspace);
 
-use crate::{Dbref, Dbref2};
+use crate::{ast::types::Continuation, Database, Dbref, Dbref2, IdCode};
 
 use super::ast::types::ModificationType;
 
@@ -428,7 +731,7 @@ make_token_tagger!(plasmid);
 make_token_tagger!(gene);
 make_token_tagger!(expression_system);
 make_token_tagger!(expression_system_common);
-make_token_tagger!(expression_system_tax_id);
+make_token_tagger!(expression_system_taxid);
 make_token_tagger!(expression_system_strain);
 make_token_tagger!(expression_system_variant);
 make_token_tagger!(expression_system_cell_line);
@@ -447,10 +750,20 @@ pub fn till_line_ending(s: &[u8]) -> IResult<&[u8], &[u8]> {
     take_till(|c| char::from(c) == '\r' || char::from(c) == '\n')(s)
 }
 
+/// Trimmed text of the 0 based byte range `from..to` of `line`, clamped to its length.
+/// Used for trailing columns which producers may leave out.
+pub(crate) fn text_field(line: &[u8], from: usize, to: usize) -> &str {
+    line.get(from.min(line.len())..to.min(line.len()))
+        .and_then(|f| str::from_utf8(f).ok())
+        .unwrap_or("")
+        .trim()
+}
+
 named!(pub residue_parser<String>, map_res!(alt!(take_str!(3) | take_str!(2) | take_str!(1)), str::FromStr::from_str));
 
+/// parses space separated residue names of any length, such as `GLY`, `DA` or `U`
 pub fn residue_list_parser(s: &[u8]) -> IResult<&[u8], Vec<String>> {
-    separated_list(multispace1, residue_parser)(s)
+    many0(preceded(space0, alphanum_word))(s)
 }
 
 #[cfg(test)]
@@ -462,7 +775,22 @@ mod test {
     fn test_date_parser() {
         let temp: NaiveDate = date_parser("12-SEP-09".as_bytes()).unwrap().1;
         assert_eq!(temp.day(), 12);
-        assert_eq!(temp.year(), 9);
+        assert_eq!(temp.year(), 2009);
+        let temp: NaiveDate = date_parser("20-APR-99".as_bytes()).unwrap().1;
+        assert_eq!(temp.year(), 1999);
+    }
+
+    #[test]
+    fn semicolon_list() {
+        let (rest, res) =
+            semicolon_separated(alphanumeric1)("  A;B  ;   C ;D;  ".as_bytes()).unwrap();
+        assert_eq!(res, vec![&b"A"[..], b"B", b"C", b"D"]);
+        assert!(rest.is_empty());
+        let (_, res) = structural_annotation_list_parser(
+            " CA ATOMS ONLY, CHAIN A ;MINIMIZED AVERAGE".as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(res, vec!["CA ATOMS ONLY, CHAIN A", "MINIMIZED AVERAGE"]);
     }
 
     #[test]
@@ -522,9 +850,9 @@ mod test {
             Ok((_, ann)) => {
                 assert_eq!(
                     ann[0],
-                    "CA ATOMS ONLY, CHAIN A, B, C, D, E, F, G, H, I, J, K "
+                    "CA ATOMS ONLY, CHAIN A, B, C, D, E, F, G, H, I, J, K"
                 );
-                assert_eq!(ann[1], " P ATOMS ONLY, CHAIN X, Y, Z");
+                assert_eq!(ann[1], "P ATOMS ONLY, CHAIN X, Y, Z");
             }
             Err(e) => {
                 println!("{:?}", e);
@@ -542,6 +870,13 @@ mod test {
         }
     }
 
+    #[test]
+    fn padded() {
+        assert_eq!(super::padded_integer(b"  42  \n"), Ok((&b"\n"[..], 42)));
+        assert_eq!(super::padded_integer(b"42\n"), Ok((&b"\n"[..], 42)));
+        assert!(super::padded_integer(b"  \n").is_err());
+    }
+
     #[test]
     fn parseint() {
         let a = "  7".trim().parse::<u32>();
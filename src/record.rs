@@ -1,64 +1,84 @@
 use super::ast::{pdb_file::*, types::*};
-use nom::{alt, complete, fold_many0, map, named, IResult};
+use nom::{
+    alt,
+    character::complete::{line_ending, space0},
+    do_parse, fold_many0, many0, map, named, not, IResult,
+};
+use std::{borrow::Cow, ops::ControlFlow, str};
 
 use super::{
+    atom::atom_with_anisou_parser,
     author::author_record_parser,
     caveat::caveat_record_parser,
+    cispep::cispep_record_parser,
     compnd::cmpnd_token_parser,
+    conect::conect_record_parser,
     dbref::dbref_record_parser,
     dbref1::dbref_partial_parser,
     expdta::expdata_record_parser,
+    formul::formul_record_parser,
+    ftnote::ftnote_record_parser,
     header::header_parser,
+    helix::helix_record_parser,
+    het::het_record_parser,
+    hetnam::hetnam_record_parser,
     jrnl::{
         jrnl_author_record_parser, jrnl_doi_record_parser, jrnl_edit_record_parser,
         jrnl_pmid_record_parser, jrnl_publ_record_parser, jrnl_ref_record_parser,
         jrnl_refn_record_parser, jrnl_title_record_parser,
     },
     keywds::keywds_parser,
+    master::master_record_parser,
     mdltyp::mdltyp_record_parser,
+    model::{model_record_parser, ter_record_parser},
     nummdl::nummdl_record_parser,
     obslte::obslte_record_parser,
-    remark::remark_record_parser,
+    options::{
+        expand_tabs, find_long_line, normalize_coordinates, regroup_order, shift_record_names,
+        ParseOptions, RECORD_NAME_COLUMNS,
+    },
+    primitive::{atom, hetatm, model},
+    remark::{
+        remark2_record_parser, remark350_record_parser, remark3_record_parser,
+        remark465_record_parser, remark4_record_parser, remark800_record_parser,
+        remark_record_parser,
+    },
     revdat::revdat_record_parser,
     seqadv::seqadv_record_parser,
+    seqres::seqres_record_parser,
+    sheet::sheet_record_parser,
+    site::site_record_parser,
     source::source_token_parser,
     split::split_record_parser,
     sprsde::sprsde_record_parser,
+    ssbond::ssbond_record_parser,
     title::title_record_parser,
+    transform::{
+        cryst1_record_parser, mtrix_record_parser, origx_record_parser, scale_record_parser,
+    },
 };
 
 named!(
+    blank_line_parser<()>,
+    do_parse!(space0 >> line_ending >> (()))
+);
+
+named!(
+    #[doc="parses a single record, skipping blank lines preceding it"],
     pdb_record_parser<Record>,
-    alt!(
-        complete!(header_parser)
-            | complete!(obslte_record_parser)
-            | complete!(title_record_parser)
-            | complete!(split_record_parser)
-            | complete!(caveat_record_parser)
-            | complete!(sprsde_record_parser)
-            | complete!(cmpnd_token_parser)
-            | complete!(source_token_parser)
-            | complete!(keywds_parser)
-            | complete!(expdata_record_parser)
-            | complete!(nummdl_record_parser)
-            | complete!(mdltyp_record_parser)
-            | complete!(author_record_parser)
-            | complete!(revdat_record_parser)
-            | complete!(jrnl_author_record_parser)
-            | complete!(jrnl_title_record_parser)
-            | complete!(jrnl_edit_record_parser)
-            | complete!(jrnl_refn_record_parser)
-            | complete!(jrnl_ref_record_parser)
-            | complete!(jrnl_publ_record_parser)
-            | complete!(jrnl_pmid_record_parser)
-            | complete!(jrnl_doi_record_parser)
-            | complete!(dbref_record_parser)
-            | complete!(dbref_partial_parser)
-            | complete!(seqadv_record_parser)
-            | complete!(remark_record_parser)
-    )
+    do_parse!(many0!(blank_line_parser) >> r: record_parser >> (r))
 );
 
+/// parses a single record with the first parser of `RECORD_PARSERS` whose record name prefix
+/// matches
+fn record_parser(s: &[u8]) -> IResult<&[u8], Record> {
+    RECORD_PARSERS
+        .iter()
+        .filter(|(_, name, _)| s.starts_with(name))
+        .find_map(|(_, _, parser)| nom::combinator::complete(*parser)(s).ok())
+        .ok_or(nom::Err::Error((s, nom::error::ErrorKind::Alt)))
+}
+
 named!(
     pdb_records_parser<PdbFile<Vec<Record>>>,
     map!(
@@ -70,11 +90,518 @@ named!(
     )
 );
 
+named!(
+    #[doc="parses a single record of the header section, skipping blank lines preceding it"],
+    header_record_parser<Record>,
+    do_parse!(
+        many0!(blank_line_parser) >> not!(alt!(atom | hetatm | model)) >> r: record_parser >> (r)
+    )
+);
+
+named!(
+    header_section_parser<PdbFile<Vec<Record>>>,
+    map!(
+        fold_many0!(header_record_parser, Vec::new(), |mut acc, r: Record| {
+            acc.push(r);
+            acc
+        }),
+        |vr: Vec<Record>| vr.to_pdb_file()
+    )
+);
+
 /// main parse function
 pub fn parse(s: &str) -> IResult<&[u8], PdbFile<Vec<Record>>> {
     pdb_records_parser(s.as_bytes())
 }
 
+/// parses `s` after applying the pre-passes enabled in `options`. Unlike [parse](fn.parse.html)
+/// input may be rewritten, so only parsed records are returned along with the non fatal
+/// issues found in them. Parsing stops at the first record which can not be parsed, the line
+/// it starts at is reported by an `UnparsedInput` warning. Input is cut before the first line
+/// longer than `max_line_length`, which is reported by a `LineTooLong` warning
+pub fn parse_with_options(s: &str, options: &ParseOptions) -> ParseResult {
+    let long_line = find_long_line(s, options.max_line_length);
+    let s = long_line.map_or(s, |(_, _, offset)| &s[..offset]);
+    let input = prepare(s, options);
+    let mut res = parse_prepared(s, &input, options).0;
+    if let Some((line, length, _)) = long_line {
+        res.warnings.push(ParseWarning::LineTooLong { line, length });
+    }
+    res
+}
+
+/// input rewritten by the pre-passes, along with the original position of each of its
+/// lines when these were reordered
+struct Prepared<'a> {
+    text: Cow<'a, str>,
+    order: Option<Vec<usize>>,
+}
+
+impl Prepared<'_> {
+    /// line of the original input, numbered from 1, the given line of the prepared input
+    /// comes from
+    fn original_line(&self, line: usize) -> usize {
+        self.order
+            .as_ref()
+            .and_then(|order| order.get(line - 1))
+            .map_or(line, |i| i + 1)
+    }
+}
+
+/// applies the rewriting pre-passes enabled in `options` to `s`
+fn prepare<'a>(s: &'a str, options: &ParseOptions) -> Prepared<'a> {
+    let mut input = if options.expand_tabs {
+        Cow::Owned(expand_tabs(s))
+    } else {
+        Cow::Borrowed(s)
+    };
+    if options.record_name_columns != RECORD_NAME_COLUMNS {
+        input = Cow::Owned(shift_record_names(&input, &options.record_name_columns));
+    }
+    let mut order = None;
+    if options.regroup_continuations {
+        let lines = input.split_inclusive('\n').collect::<Vec<_>>();
+        let regrouped = regroup_order(&lines);
+        let text = regrouped.iter().map(|i| lines[*i]).collect::<String>();
+        order = Some(regrouped);
+        input = Cow::Owned(text);
+    }
+    if options.scientific_coordinates {
+        input = Cow::Owned(normalize_coordinates(&input));
+    }
+    Prepared { text: input, order }
+}
+
+/// parses `input` prepared from `s`, returning the input left unparsed along with the result
+fn parse_prepared<'a>(
+    s: &str,
+    input: &'a Prepared,
+    options: &ParseOptions,
+) -> (ParseResult, &'a [u8]) {
+    let bytes = input.text.as_bytes();
+    let (pdb, rest) = if options.keep_raw {
+        let (records, spans, rest) = pdb_records_raw_parser(bytes);
+        let source = s
+            .split_inclusive('\n')
+            .map(|l| l.trim_end_matches(['\r', '\n']))
+            .collect::<Vec<_>>();
+        let raw = spans
+            .into_iter()
+            .map(|span| {
+                let lines = span
+                    .into_iter()
+                    .map(|line| input.original_line(line))
+                    .collect::<Vec<_>>();
+                RawLines {
+                    line: lines.first().copied().unwrap_or_default(),
+                    lines: lines
+                        .iter()
+                        .filter_map(|line| source.get(line - 1))
+                        .map(|line| (*line).to_owned())
+                        .collect(),
+                }
+            })
+            .collect();
+        (PdbFile::with_raw(records, raw), rest)
+    } else {
+        match pdb_records_parser(bytes) {
+            Ok((rest, pdb)) => (pdb, rest),
+            Err(_) => (Vec::new().to_pdb_file(), bytes),
+        }
+    };
+    let mut warnings = pdb.warnings();
+    if let Some(line) = unparsed_line(bytes, rest) {
+        warnings.push(ParseWarning::UnparsedInput {
+            line: input.original_line(line),
+        });
+    }
+    (ParseResult { warnings, pdb }, rest)
+}
+
+/// line, numbered from 1, at which `rest` left unparsed from `input` starts. `None` when
+/// only blank lines, possibly following the END record closing the entry, are left
+fn unparsed_line(input: &[u8], rest: &[u8]) -> Option<usize> {
+    let rest = skip_blank_lines(rest);
+    if rest
+        .strip_prefix(b"END")
+        .unwrap_or(rest)
+        .iter()
+        .all(u8::is_ascii_whitespace)
+    {
+        return None;
+    }
+    let consumed = &input[..input.len() - rest.len()];
+    Some(consumed.iter().filter(|c| **c == b'\n').count() + 1)
+}
+
+type RecordParser = fn(&[u8]) -> IResult<&[u8], Record>;
+
+/// kinds of the single line records whose failures can be located
+const LOCATED_KINDS: [RecordKind; 10] = [
+    RecordKind::Atom,
+    RecordKind::Het,
+    RecordKind::Helix,
+    RecordKind::Sheet,
+    RecordKind::SsBond,
+    RecordKind::CisPep,
+    RecordKind::Cryst1,
+    RecordKind::Conect,
+    RecordKind::Model,
+    RecordKind::Ter,
+];
+
+/// column, numbered from 1, at which the record starting `rest` stops parsing. Only records
+/// of `LOCATED_KINDS` are located
+fn malformed_column(rest: &[u8]) -> Option<usize> {
+    let (_, _, parser) = RECORD_PARSERS
+        .iter()
+        .find(|(kind, name, _)| LOCATED_KINDS.contains(kind) && rest.starts_with(name))?;
+    match parser(rest) {
+        Ok(_) => None,
+        Err(nom::Err::Error((i, _))) | Err(nom::Err::Failure((i, _))) => {
+            Some(rest.len() - i.len() + 1)
+        }
+        Err(nom::Err::Incomplete(_)) => Some(
+            rest.iter()
+                .position(|c| *c == b'\n' || *c == b'\r')
+                .unwrap_or(rest.len())
+                + 1,
+        ),
+    }
+}
+
+/// parses records preceding the coordinate section, stopping at the first ATOM, HETATM
+/// or MODEL record. Cheaper than [parse](fn.parse.html) when only metadata is needed. A
+/// record before the coordinate section which can not be parsed is returned as an error
+pub fn parse_header_only(input: &[u8]) -> Result<PdbFile<Vec<Record>>, crate::ProteinError> {
+    let input = str::from_utf8(input)?.as_bytes();
+    let (rest, pdb) = match header_section_parser(input) {
+        Ok(parsed) => parsed,
+        Err(_) => (input, Vec::new().to_pdb_file()),
+    };
+    let coordinates = skip_blank_lines(rest);
+    match unparsed_line(input, rest) {
+        Some(line)
+            if ![&b"ATOM"[..], b"HETATM", b"MODEL"]
+                .iter()
+                .any(|name| coordinates.starts_with(name)) =>
+        {
+            Err(crate::ProteinError::MalformedRecord {
+                line,
+                column: malformed_column(coordinates),
+            })
+        }
+        _ => Ok(pdb),
+    }
+}
+
+/// parses `s` as [parse_with_options](fn.parse_with_options.html) does. Lines longer than
+/// the `max_line_length` option and input left unparsed are returned as errors, the latter
+/// along with the failing column for records whose failure can be located. With `strict`
+/// option set structural problems are returned as errors instead of warnings
+pub fn try_parse_with_options(
+    s: &str,
+    options: &ParseOptions,
+) -> Result<ParseResult, crate::ProteinError> {
+    if let Some((line, length, _)) = find_long_line(s, options.max_line_length) {
+        return Err(crate::ProteinError::LineTooLong { line, length });
+    }
+    let input = prepare(s, options);
+    let (res, rest) = parse_prepared(s, &input, options);
+    if let Some(line) = unparsed_line(input.text.as_bytes(), rest) {
+        return Err(crate::ProteinError::MalformedRecord {
+            line: input.original_line(line),
+            column: malformed_column(skip_blank_lines(rest)),
+        });
+    }
+    if options.strict {
+        if let Some(missing) = res.warnings.iter().find_map(|w| match w {
+            ParseWarning::ModelGap { missing } => Some(*missing),
+            _ => None,
+        }) {
+            return Err(crate::ProteinError::ModelGap { missing });
+        }
+    }
+    Ok(res)
+}
+
+/// parses raw bytes such as a file read from disk. Input is expected to be UTF-8, with
+/// `lossy_utf8` option set invalid sequences are replaced by U+FFFD, otherwise only the
+/// lines preceding the first invalid sequence are parsed and the line holding it is
+/// reported by an `UnparsedInput` warning
+pub fn parse_bytes_with_options(s: &[u8], options: &ParseOptions) -> ParseResult {
+    if options.lossy_utf8 {
+        parse_with_options(&String::from_utf8_lossy(s), options)
+    } else {
+        match str::from_utf8(s) {
+            Ok(s) => parse_with_options(s, options),
+            Err(e) => {
+                let valid = &s[..e.valid_up_to()];
+                let lines = valid
+                    .iter()
+                    .rposition(|c| *c == b'\n')
+                    .map_or(&valid[..0], |end| &valid[..=end]);
+                let mut res =
+                    parse_with_options(str::from_utf8(lines).unwrap_or_default(), options);
+                if !res
+                    .warnings
+                    .iter()
+                    .any(|w| matches!(w, ParseWarning::UnparsedInput { .. }))
+                {
+                    res.warnings.push(ParseWarning::UnparsedInput {
+                        line: lines.iter().filter(|c| **c == b'\n').count() + 1,
+                    });
+                }
+                res
+            }
+        }
+    }
+}
+
+/// parses the file at `path` through a memory map instead of reading it into memory first,
+/// which keeps peak memory low for very large entries. Input must be valid UTF-8. A record
+/// which can not be parsed is returned as an error
+#[cfg(feature = "mmap")]
+pub fn parse_pdb_mmap<P: AsRef<std::path::Path>>(
+    path: P,
+) -> Result<PdbFile<Vec<Record>>, crate::ProteinError> {
+    let file = std::fs::File::open(path)?;
+    // SAFETY: the map is only read while parsing and records own their data, so
+    // nothing borrows from it once it is unmapped at the end of this function
+    let map = unsafe { memmap2::Mmap::map(&file)? };
+    let input = str::from_utf8(&map)?.as_bytes();
+    let (rest, pdb) = match pdb_records_parser(input) {
+        Ok(parsed) => parsed,
+        Err(_) => (input, Vec::new().to_pdb_file()),
+    };
+    match unparsed_line(input, rest) {
+        Some(line) => Err(crate::ProteinError::MalformedRecord {
+            line,
+            column: malformed_column(skip_blank_lines(rest)),
+        }),
+        None => Ok(pdb),
+    }
+}
+
+/// parses `input` record by record as [parse](fn.parse.html) does, handing each record to `f`
+/// instead of collecting them. Records of a MODEL block are not handed one by one, the
+/// whole block arrives as a single `Record::Model` holding its atoms. Parsing stops once `f`
+/// returns `ControlFlow::Break`, which is not an error. Input left unparsed otherwise is
+/// returned as an error, after the records preceding it were handed to `f`
+pub fn parse_pdb_each<F: FnMut(Record) -> ControlFlow<()>>(
+    input: &[u8],
+    mut f: F,
+) -> Result<(), crate::ProteinError> {
+    let mut remaining = input;
+    while let Ok((rest, record)) = pdb_record_parser(remaining) {
+        if rest.len() == remaining.len() {
+            break;
+        }
+        if f(record).is_break() {
+            return Ok(());
+        }
+        remaining = rest;
+    }
+    match unparsed_line(input, remaining) {
+        Some(line) => Err(crate::ProteinError::MalformedRecord {
+            line,
+            column: malformed_column(skip_blank_lines(remaining)),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// parses `s` as [parse](fn.parse.html) does, pairing each record with the number of
+/// physical lines it was folded from. Blank lines between records are not counted
+pub fn parse_with_line_counts(s: &str) -> Vec<(Record, usize)> {
+    let (records, spans, _) = pdb_records_raw_parser(s.as_bytes());
+    records
+        .into_iter()
+        .zip(spans.iter().map(Vec::len))
+        .collect()
+}
+
+/// kind of the record produced, record name prefix and parser of each record, in the order
+/// `record_parser` tries them
+const RECORD_PARSERS: [(RecordKind, &[u8], RecordParser); 52] = [
+    (RecordKind::Header, b"HEADER", header_parser),
+    (RecordKind::Obslte, b"OBSLTE", obslte_record_parser),
+    (RecordKind::Title, b"TITLE ", title_record_parser),
+    (RecordKind::Split, b"SPLIT ", split_record_parser),
+    (RecordKind::Caveat, b"CAVEAT", caveat_record_parser),
+    (RecordKind::Sprsde, b"SPRSDE", sprsde_record_parser),
+    (RecordKind::Cmpnd, b"COMPND", cmpnd_token_parser),
+    (RecordKind::Source, b"SOURCE", source_token_parser),
+    (RecordKind::Keywds, b"KEYWDS", keywds_parser),
+    (RecordKind::Experimental, b"EXPDTA", expdata_record_parser),
+    (RecordKind::Nummdl, b"NUMMDL", nummdl_record_parser),
+    (RecordKind::Mdltyp, b"MDLTYP", mdltyp_record_parser),
+    (RecordKind::Authors, b"AUTHOR", author_record_parser),
+    (RecordKind::Revdats, b"REVDAT", revdat_record_parser),
+    (
+        RecordKind::JournalAuthors,
+        b"JRNL  ",
+        jrnl_author_record_parser,
+    ),
+    (
+        RecordKind::JournalTitle,
+        b"JRNL  ",
+        jrnl_title_record_parser,
+    ),
+    (
+        RecordKind::JournalEditors,
+        b"JRNL  ",
+        jrnl_edit_record_parser,
+    ),
+    (
+        RecordKind::JournalCitation,
+        b"JRNL  ",
+        jrnl_refn_record_parser,
+    ),
+    (
+        RecordKind::JournalReference,
+        b"JRNL  ",
+        jrnl_ref_record_parser,
+    ),
+    (
+        RecordKind::JournalPublication,
+        b"JRNL  ",
+        jrnl_publ_record_parser,
+    ),
+    (
+        RecordKind::JournalPubMedId,
+        b"JRNL  ",
+        jrnl_pmid_record_parser,
+    ),
+    (RecordKind::JournalDoi, b"JRNL  ", jrnl_doi_record_parser),
+    (RecordKind::Dbref, b"DBREF ", dbref_record_parser),
+    (RecordKind::Dbref, b"DBREF1", dbref_partial_parser),
+    (RecordKind::Seqadv, b"SEQADV", seqadv_record_parser),
+    (RecordKind::Seqres, b"SEQRES", seqres_record_parser),
+    (RecordKind::Het, b"HET   ", het_record_parser),
+    (RecordKind::HetName, b"HETNAM", hetnam_record_parser),
+    (RecordKind::Formul, b"FORMUL", formul_record_parser),
+    (RecordKind::Helix, b"HELIX ", helix_record_parser),
+    (RecordKind::Sheet, b"SHEET ", sheet_record_parser),
+    (RecordKind::SsBond, b"SSBOND", ssbond_record_parser),
+    (RecordKind::CisPep, b"CISPEP", cispep_record_parser),
+    (RecordKind::Site, b"SITE  ", site_record_parser),
+    (RecordKind::Model, b"MODEL ", model_record_parser),
+    (RecordKind::Atom, b"ATOM  ", atom_with_anisou_parser),
+    (RecordKind::Atom, b"HETATM", atom_with_anisou_parser),
+    (RecordKind::Ter, b"TER", ter_record_parser),
+    (RecordKind::Conect, b"CONECT", conect_record_parser),
+    (RecordKind::FootNote, b"FTNOTE", ftnote_record_parser),
+    (RecordKind::Cryst1, b"CRYST1", cryst1_record_parser),
+    (RecordKind::Origx, b"ORIGX", origx_record_parser),
+    (RecordKind::Scale, b"SCALE", scale_record_parser),
+    (RecordKind::Mtrix, b"MTRIX", mtrix_record_parser),
+    (RecordKind::Resolution, b"REMARK   2", remark2_record_parser),
+    (
+        RecordKind::RefinementStats,
+        b"REMARK   3",
+        remark3_record_parser,
+    ),
+    (
+        RecordKind::FormatVersion,
+        b"REMARK   4",
+        remark4_record_parser,
+    ),
+    (
+        RecordKind::BiologicalAssemblies,
+        b"REMARK 350",
+        remark350_record_parser,
+    ),
+    (
+        RecordKind::MissingResidues,
+        b"REMARK 465",
+        remark465_record_parser,
+    ),
+    (
+        RecordKind::SiteDescriptions,
+        b"REMARK 800",
+        remark800_record_parser,
+    ),
+    (RecordKind::Remark, b"REMARK", remark_record_parser),
+    (RecordKind::Master, b"MASTER", master_record_parser),
+];
+
+/// parses only records of the given `kinds`. Lines of other records are skipped by their
+/// record name without being parsed, which makes reading a few header records of a large
+/// entry cheap. Lines of requested kinds which fail to parse are skipped as well. Records
+/// sharing a record name prefix are told apart as [parse](fn.parse.html) does, so selecting
+/// `Remark` leaves out the REMARK blocks parsed into typed records such as `Resolution`
+pub fn parse_pdb_selective(input: &str, kinds: &[RecordKind]) -> PdbFile<Vec<Record>> {
+    let selected = RECORD_PARSERS
+        .iter()
+        .filter(|(kind, _, _)| kinds.contains(kind))
+        .map(|(_, name, _)| *name)
+        .collect::<Vec<_>>();
+    let parsers = RECORD_PARSERS
+        .iter()
+        .filter(|(_, name, _)| {
+            selected
+                .iter()
+                .any(|s| s.starts_with(name) || name.starts_with(s))
+        })
+        .collect::<Vec<_>>();
+    let mut records = Vec::new();
+    let mut remaining = input.as_bytes();
+    while !remaining.is_empty() {
+        let parsed = parsers
+            .iter()
+            .filter(|(_, name, _)| remaining.starts_with(name))
+            .find_map(|(_, _, parser)| nom::combinator::complete(*parser)(remaining).ok());
+        remaining = match parsed {
+            Some((rest, record)) if rest.len() < remaining.len() => {
+                if kinds.contains(&record.kind()) {
+                    records.push(record);
+                }
+                rest
+            }
+            _ => remaining
+                .iter()
+                .position(|c| *c == b'\n')
+                .map_or(&remaining[remaining.len()..], |end| &remaining[end + 1..]),
+        };
+    }
+    records.to_pdb_file()
+}
+
+/// parses records one at a time, pairing each with the numbers, counted from 1, of the
+/// non blank lines it consumed
+fn pdb_records_raw_parser(s: &[u8]) -> (Vec<Record>, Vec<Vec<usize>>, &[u8]) {
+    let mut records = Vec::new();
+    let mut spans = Vec::new();
+    let mut remaining = s;
+    let mut line = 1;
+    while let Ok((rest, record)) = pdb_record_parser(remaining) {
+        if rest.len() == remaining.len() {
+            break;
+        }
+        let consumed = &remaining[..remaining.len() - rest.len()];
+        let mut span = Vec::new();
+        for l in consumed.split_inclusive(|c| *c == b'\n') {
+            if !l.iter().all(u8::is_ascii_whitespace) {
+                span.push(line);
+            }
+            line += 1;
+        }
+        spans.push(span);
+        records.push(record);
+        remaining = rest;
+    }
+    (records, spans, remaining)
+}
+
+/// `s` without its leading blank lines
+fn skip_blank_lines(s: &[u8]) -> &[u8] {
+    match many0!(s, blank_line_parser) {
+        Ok((rest, _)) => rest,
+        Err(_) => s,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -230,6 +757,504 @@ JRNL        DOI    10.1073/PNAS.97.7.3171
         }
     }
 
+    #[test]
+    fn tab_expansion() {
+        let input = "COMPND\tMOL_ID: 1;\nCOMPND   2 MOLECULE: HEMOGLOBIN\tALPHA CHAIN\n";
+        let mut res = super::parse_with_options(
+            input,
+            &ParseOptions {
+                expand_tabs: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            res.pdb.header().cmpnd().unwrap().tokens,
+            vec![
+                Token::MoleculeId(1),
+                Token::Molecule("HEMOGLOBIN ALPHA CHAIN".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn each_record() {
+        let input = r#"HEADER    HYDROLASE                               20-APR-99   1CJY
+ATOM      1  N   MET A   1      47.699  22.521  30.384  1.00 37.82           N
+ATOM      2  CA  MET A   1      48.661  22.866  29.347  1.00 27.31           C
+TER       3      MET A   1
+ATOM      4  N   GLY B   1      49.020  23.110  28.113  1.00 25.12           N
+"#;
+        let mut atoms = 0;
+        let mut seen = 0;
+        let res = parse_pdb_each(input.as_bytes(), |record| {
+            seen += 1;
+            match record {
+                Record::Atom(_) => atoms += 1,
+                Record::Ter(_) => return ControlFlow::Break(()),
+                _ => {}
+            }
+            ControlFlow::Continue(())
+        });
+        assert!(res.is_ok());
+        assert_eq!(atoms, 2);
+        assert_eq!(seen, 4);
+
+        let mut seen = 0;
+        let res = parse_pdb_each(input.as_bytes(), |_| {
+            seen += 1;
+            ControlFlow::Continue(())
+        });
+        assert!(res.is_ok());
+        assert_eq!(seen, 5);
+
+        let malformed = format!("{}ATOM      5  CA  GLY B   1      4x.020\n", input);
+        let mut seen = 0;
+        let res = parse_pdb_each(malformed.as_bytes(), |_| {
+            seen += 1;
+            ControlFlow::Continue(())
+        });
+        assert_eq!(seen, 5);
+        assert!(matches!(
+            res,
+            Err(crate::ProteinError::MalformedRecord { line: 6, .. })
+        ));
+
+        let models = r#"MODEL        1
+ATOM      1  N   MET A   1      47.699  22.521  30.384  1.00 37.82           N
+ATOM      2  CA  MET A   1      48.661  22.866  29.347  1.00 27.31           C
+ENDMDL
+MODEL        2
+ATOM      1  N   MET A   1      47.699  22.521  30.384  1.00 37.82           N
+ENDMDL
+"#;
+        let mut sizes = Vec::new();
+        let res = parse_pdb_each(models.as_bytes(), |record| {
+            if let Record::Model(m) = record {
+                sizes.push(m.atoms.len());
+            }
+            ControlFlow::Continue(())
+        });
+        assert!(res.is_ok());
+        assert_eq!(sizes, vec![2, 1]);
+    }
+
+    #[test]
+    fn scientific_coordinates() {
+        let input =
+            "ATOM      1  N   MET A   1    1.23E+01  22.521  30.384  1.00 37.82           N\n";
+        let res = super::parse_with_options(
+            input,
+            &ParseOptions {
+                scientific_coordinates: true,
+                ..Default::default()
+            },
+        );
+        match res.pdb.records() {
+            [Record::Atom(a)] => {
+                assert!((a.x - 12.3).abs() < 1e-9);
+                assert_eq!(a.y, 22.521);
+            }
+            r => panic!("{:?}", r),
+        }
+        let res = super::parse_with_options(input, &ParseOptions::default());
+        assert!(res.pdb.records().is_empty());
+        assert_eq!(res.warnings, vec![ParseWarning::UnparsedInput { line: 1 }]);
+    }
+
+    #[test]
+    fn keep_raw() {
+        let input = "HEADER    HYDROLASE                               20-APR-99   1CJY   \nCOMPND    MOL_ID: 1;\nCOMPND   2 MOLECULE: HEMOGLOBIN ALPHA CHAIN\n";
+        let res = super::parse_with_options(
+            input,
+            &ParseOptions {
+                keep_raw: true,
+                ..Default::default()
+            },
+        );
+        let (record, raw) = res.pdb.records_with_raw().nth(1).unwrap();
+        assert!(matches!(record, Record::Cmpnd(_)));
+        assert_eq!(
+            raw.unwrap(),
+            &RawLines {
+                line: 2,
+                lines: vec![
+                    "COMPND    MOL_ID: 1;".to_owned(),
+                    "COMPND   2 MOLECULE: HEMOGLOBIN ALPHA CHAIN".to_owned()
+                ]
+            }
+        );
+
+        let res = super::parse_with_options(input, &ParseOptions::default());
+        assert!(res.pdb.records_with_raw().all(|(_, raw)| raw.is_none()));
+    }
+
+    #[test]
+    fn keep_raw_original_lines() {
+        let input =
+            "COMPND    MOL_ID: 1;\nTITLE     STRAY TITLE\n\nCOMPND   2 MOLECULE:\tLYSOZYME;\n";
+        let res = super::parse_with_options(
+            input,
+            &ParseOptions {
+                keep_raw: true,
+                expand_tabs: true,
+                regroup_continuations: true,
+                ..Default::default()
+            },
+        );
+        let raw = res
+            .pdb
+            .records_with_raw()
+            .map(|(_, raw)| raw.unwrap().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            raw,
+            vec![
+                RawLines {
+                    line: 1,
+                    lines: vec![
+                        "COMPND    MOL_ID: 1;".to_owned(),
+                        "COMPND   2 MOLECULE:\tLYSOZYME;".to_owned()
+                    ]
+                },
+                RawLines {
+                    line: 2,
+                    lines: vec!["TITLE     STRAY TITLE".to_owned()]
+                }
+            ]
+        );
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn mmap() {
+        let mut res = super::parse_pdb_mmap(get_test_file_path("1BYI.pdb")).unwrap();
+        assert_eq!(res.header().header().unwrap().id_code, "1BYI");
+        // MODRES records of 1BXO are not parsed
+        assert!(matches!(
+            super::parse_pdb_mmap(get_test_file_path("1BXO.pdb")),
+            Err(crate::ProteinError::MalformedRecord { line: 536, .. })
+        ));
+        assert!(super::parse_pdb_mmap(get_test_file_path("missing.pdb")).is_err());
+
+        let path = std::env::temp_dir().join(format!("patoz-mmap-{}.pdb", std::process::id()));
+        std::fs::write(
+            &path,
+            "TITLE     HEMOGLOBIN\nHELIX    1  HA GLY A   x\nKEYWDS    OXYGEN TRANSPORT\n",
+        )
+        .unwrap();
+        let res = super::parse_pdb_mmap(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            res,
+            Err(crate::ProteinError::MalformedRecord { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn blank_lines() {
+        let (rest, mut res) =
+            super::parse("TITLE     HEMOGLOBIN\n\n   \nKEYWDS    OXYGEN TRANSPORT\n\n").unwrap();
+        assert_eq!(res.header().title().unwrap().title, "HEMOGLOBIN");
+        assert_eq!(
+            res.header().keywds().unwrap().keywords,
+            vec!["OXYGEN TRANSPORT".to_owned()]
+        );
+        assert_eq!(rest, b"\n");
+    }
+
+    #[test]
+    fn header_only() {
+        let input = r#"TITLE     HEMOGLOBIN
+COMPND    MOL_ID: 1;
+COMPND   2 MOLECULE: HEMOGLOBIN ALPHA CHAIN
+ATOM      1  N   ALA A   1      47.699  22.521  30.384  1.00 37.82           N
+ATOM      2  CA  ALA A   1      48.661  22.866  29.347  1.00 27.31           C
+"#;
+        let mut res = super::parse_header_only(input.as_bytes()).unwrap();
+        assert_eq!(res.header().title().unwrap().title, "HEMOGLOBIN");
+        assert!(res.header().cmpnd().is_some());
+        assert!(res.models().is_empty());
+
+        let (_, full) = super::parse(input).unwrap();
+        assert_eq!(full.models()[0].atoms.len(), 2);
+
+        let mut res = super::parse_header_only(
+            "TITLE     HEMOGLOBIN\n\nATOM      1  N   ALA A   1      47.699  22.521  30.384  1.00 37.82           N\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(res.header().title().unwrap().title, "HEMOGLOBIN");
+        assert!(res.models().is_empty());
+
+        assert!(matches!(
+            super::parse_header_only(b"HEADER    garbage\nTITLE     X\n"),
+            Err(crate::ProteinError::MalformedRecord { line: 1, .. })
+        ));
+        assert!(matches!(
+            super::parse_header_only(
+                format!("TITLE     HEMOGLOBIN\nHELIX    1  HA GLY A   x\n{}", input).as_bytes()
+            ),
+            Err(crate::ProteinError::MalformedRecord { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn shifted_record_names() {
+        let input = "  HEADER    HYDROLASE                               20-APR-99   1CJY   \n  KEYWDS    HYDROLASE\n";
+        let mut res = super::parse_with_options(input, &ParseOptions::default());
+        assert!(res.pdb.header().header().is_none());
+
+        let mut res = super::parse_with_options(
+            input,
+            &ParseOptions {
+                record_name_columns: 3..=8,
+                ..Default::default()
+            },
+        );
+        assert_eq!(res.pdb.header().header().unwrap().id_code, "1CJY");
+        assert_eq!(
+            res.pdb.header().keywds().unwrap().keywords,
+            vec!["HYDROLASE".to_owned()]
+        );
+    }
+
+    #[test]
+    fn interleaved_continuation() {
+        let input = "COMPND    MOL_ID: 1;\nTITLE     STRAY TITLE\nCOMPND   2 MOLECULE: LYSOZYME;\n";
+        let mut res = super::parse_with_options(input, &ParseOptions::default());
+        assert_eq!(
+            res.pdb.header().cmpnd().unwrap().tokens,
+            vec![Token::MoleculeId(1), Token::Molecule("LYSOZYME".to_owned())]
+        );
+        assert_eq!(res.pdb.header().title().unwrap().title, "STRAY TITLE");
+        assert_eq!(res.pdb.records().len(), 2);
+    }
+
+    #[test]
+    fn site_description() {
+        let (_, pdb) = super::parse(
+            r#"REMARK 800
+REMARK 800 SITE_IDENTIFIER: AC1
+REMARK 800 EVIDENCE_CODE: SOFTWARE
+REMARK 800 SITE_DESCRIPTION: BINDING SITE FOR RESIDUE ZN A 301
+SITE     1 AC1  3 HIS A  94  HIS A  96  HIS A 119
+"#,
+        )
+        .unwrap();
+        match pdb.records().last() {
+            Some(Record::Site(site)) => assert_eq!(
+                pdb.site_description(site).unwrap().description,
+                "BINDING SITE FOR RESIDUE ZN A 301"
+            ),
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn line_length_guard() {
+        let input = format!("KEYWDS    HYDROLASE\n{}", "A".repeat(10 * 1024));
+        let res = super::try_parse_with_options(&input, &ParseOptions::default());
+        assert!(matches!(
+            res,
+            Err(crate::ProteinError::LineTooLong {
+                line: 2,
+                length: 10240
+            })
+        ));
+
+        let mut res = super::parse_with_options(&input, &ParseOptions::default());
+        assert!(res.pdb.header().keywds().is_some());
+        assert_eq!(res.pdb.records().len(), 1);
+        assert_eq!(
+            res.warnings,
+            vec![ParseWarning::LineTooLong {
+                line: 2,
+                length: 10240
+            }]
+        );
+    }
+
+    #[test]
+    fn model_gap() {
+        let input = (1..=4)
+            .filter(|serial| *serial != 3)
+            .map(|serial| {
+                format!(
+                    "MODEL     {:>4}\nATOM      1  N   ALA A   1      47.699  22.521  30.384  1.00 37.82           N\nENDMDL\n",
+                    serial
+                )
+            })
+            .collect::<String>();
+
+        let res = super::try_parse_with_options(&input, &ParseOptions::default()).unwrap();
+        assert_eq!(res.warnings, vec![ParseWarning::ModelGap { missing: 3 }]);
+
+        let res = super::try_parse_with_options(
+            &input,
+            &ParseOptions {
+                strict: true,
+                ..Default::default()
+            },
+        );
+        assert!(matches!(
+            res,
+            Err(crate::ProteinError::ModelGap { missing: 3 })
+        ));
+    }
+
+    #[test]
+    fn malformed_column() {
+        let input = r#"KEYWDS    HYDROLASE
+
+ATOM      1  N   ALA A   1      47.699  22.521  30.384  1.00 37.82           N
+ATOM      2  CA  ALA A   1      4X.661  22.866  29.347  1.00 27.31           C
+"#;
+        let res = super::try_parse_with_options(input, &ParseOptions::default());
+        assert!(matches!(
+            res,
+            Err(crate::ProteinError::MalformedRecord {
+                line: 4,
+                column: Some(31)
+            })
+        ));
+        assert_eq!(
+            super::parse_with_options(input, &ParseOptions::default())
+                .pdb
+                .records()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn line_counts() {
+        let res = super::parse_with_line_counts(
+            r#"COMPND    MOL_ID: 1;
+COMPND   2 MOLECULE: HEMOGLOBIN ALPHA CHAIN;
+COMPND   3 CHAIN: A, C
+
+KEYWDS    OXYGEN TRANSPORT
+"#,
+        );
+        assert_eq!(
+            res.iter().map(|(_, count)| *count).collect::<Vec<_>>(),
+            vec![3, 1]
+        );
+        assert!(matches!(res[0].0, Record::Cmpnd(_)));
+    }
+
+    #[test]
+    fn selective() {
+        let atoms = (1..=1000)
+            .map(|serial| {
+                format!(
+                    "ATOM  {:>5}  CA  ALA A{:>4}      47.699  22.521  30.384  1.00 37.82           C\n",
+                    serial, serial
+                )
+            })
+            .collect::<String>();
+        let input = format!(
+            "TITLE     SELECTIVE\nCOMPND    MOL_ID: 1;\nCOMPND   2 MOLECULE: HEMOGLOBIN ALPHA CHAIN\n{}END\n",
+            atoms
+        );
+        let pdb = super::parse_pdb_selective(&input, &[RecordKind::Cmpnd]);
+        assert_eq!(pdb.records().len(), 1);
+        assert!(matches!(pdb.records()[0], Record::Cmpnd(_)));
+
+        let pdb = super::parse_pdb_selective(&input, &[RecordKind::Title, RecordKind::Atom]);
+        assert_eq!(pdb.records().len(), 1001);
+
+        let input = r#"DBREF1 1ABC A   61    322 UNIMES               UPI000148A153
+DBREF2 1ABC A     MES00005880000                     61         322 
+"#;
+        let pdb = super::parse_pdb_selective(input, &[RecordKind::Dbref]);
+        assert!(matches!(pdb.records(), [Record::Dbref(_)]));
+
+        let input = r#"REMARK   1 REFERENCE 1
+REMARK   2
+REMARK   2 RESOLUTION.    2.10 ANGSTROMS.
+REMARK 999 SEQUENCE
+"#;
+        let pdb = super::parse_pdb_selective(input, &[RecordKind::Remark]);
+        assert_eq!(
+            pdb.records(),
+            &[
+                Record::Remark("   1 REFERENCE 1".to_owned()),
+                Record::Remark("   2".to_owned()),
+                Record::Remark(" 999 SEQUENCE".to_owned())
+            ]
+        );
+        let pdb = super::parse_pdb_selective(input, &[RecordKind::Resolution]);
+        assert!(matches!(pdb.records(), [Record::Resolution(_)]));
+    }
+
+    #[test]
+    fn clone_and_debug() {
+        let (_, res) =
+            super::parse("COMPND    MOL_ID: 1;\nCOMPND   2 MOLECULE: HEMOGLOBIN ALPHA CHAIN\n")
+                .unwrap();
+        let (record, _) = res.records_with_raw().next().unwrap();
+        let cloned = record.clone();
+        assert_eq!(format!("{:?}", cloned), format!("{:?}", record));
+        assert!(format!("{:?}", cloned).contains("HEMOGLOBIN ALPHA CHAIN"));
+        assert_eq!(format!("{:?}", res.clone()), format!("{:?}", res));
+    }
+
+    #[test]
+    fn lossy_utf8() {
+        let input = b"COMPND    MOL_ID: 1;\nCOMPND   2 OTHER_DETAILS: CRYSTALS GROWN AT 4\xb0C\nKEYWDS    HYDROLASE\n";
+        let mut res = super::parse_bytes_with_options(
+            input,
+            &ParseOptions {
+                lossy_utf8: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            res.pdb.header().cmpnd().unwrap().tokens[1],
+            Token::OtherDetails("CRYSTALS GROWN AT 4\u{FFFD}C".to_owned())
+        );
+        assert!(res.pdb.header().keywds().is_some());
+        assert!(res.warnings.is_empty());
+
+        let mut res = super::parse_bytes_with_options(input, &ParseOptions::default());
+        assert_eq!(res.warnings, vec![ParseWarning::UnparsedInput { line: 2 }]);
+        assert_eq!(
+            res.pdb.header().cmpnd().unwrap().tokens,
+            vec![Token::MoleculeId(1)]
+        );
+        assert!(res.pdb.header().keywds().is_none());
+    }
+
+    #[test]
+    fn implicit_model() {
+        let (_, res) = super::parse(
+            r#"ATOM      1  N   ALA A   1      47.699  22.521  30.384  1.00 37.82           N
+ATOM      2  CA  ALA A   1      48.661  22.866  29.347  1.00 27.31           C
+TER       3      ALA A   1
+"#,
+        )
+        .unwrap();
+        let models = res.models();
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].atoms.len(), 2);
+    }
+
+    #[test]
+    fn count_mismatch_warning() {
+        let input = "SEQRES   1 A    4  MET ALA GLY                                                \nSOURCE    MOL_ID: 1;\nSOURCE   2 ORGANISM_TAXID: 9606\n";
+        let mut res = super::parse_with_options(input, &ParseOptions::default());
+        assert_eq!(
+            res.warnings,
+            vec![ParseWarning::CountMismatch {
+                record: "SEQRES",
+                expected: 4,
+                found: 3
+            }]
+        );
+        assert_eq!(res.pdb.primary().residues().unwrap().residues.len(), 3);
+    }
+
     fn get_test_file_path(file_name: &str) -> PathBuf {
         let mut current_file_path = PathBuf::from(file!());
         current_file_path.pop();
@@ -265,7 +1290,7 @@ JRNL        DOI    10.1073/PNAS.97.7.3171
         );
         assert_eq!(
             expected_val["header.id_code"],
-            pdb_parsed.header().header().unwrap().id_code
+            pdb_parsed.header().header().unwrap().id_code.as_str()
         );
         assert_eq!(
             expected_val["header.keywords"][0],
@@ -343,7 +1368,12 @@ JRNL        DOI    10.1073/PNAS.97.7.3171
 
         assert_eq!(
             expected_val["primary.dbref.database"],
-            pdb_parsed.primary().dbreference().unwrap().database
+            pdb_parsed
+                .primary()
+                .dbreference()
+                .unwrap()
+                .database
+                .to_string()
         );
     }
 
@@ -361,4 +1391,61 @@ JRNL        DOI    10.1073/PNAS.97.7.3171
     fn byi() {
         parse_from_file("1BYI")
     }
+
+    #[test]
+    fn fuzz_regressions() {
+        assert!(
+            try_parse_with_options("EXPDTA    X-RAY DIFFRACTION#\n", &ParseOptions::default())
+                .is_ok()
+        );
+        for input in [
+            "KEYWDS    #\n",
+            "HEADER    HYDROLASE                               31-FEB-99   1CJY\n",
+            "HEADER    HYDROLASE                               20-XYZ-99   1CJY\n",
+        ] {
+            assert!(matches!(
+                try_parse_with_options(input, &ParseOptions::default()),
+                Err(crate::ProteinError::MalformedRecord {
+                    line: 1,
+                    column: None
+                })
+            ));
+        }
+    }
+
+    /// record names, so that random lines reach the parsers of the records
+    const FUZZ_RECORD_NAMES: [&str; 24] = [
+        "HEADER", "TITLE ", "COMPND", "SOURCE", "KEYWDS", "EXPDTA", "AUTHOR", "REVDAT", "JRNL  ",
+        "REMARK", "DBREF ", "SEQADV", "SEQRES", "HET   ", "FORMUL", "HELIX ", "SHEET ", "SSBOND",
+        "SITE  ", "CRYST1", "MODEL ", "ATOM  ", "HETATM", "CONECT",
+    ];
+
+    fn fuzz_lines() -> impl proptest::strategy::Strategy<Value = String> {
+        use proptest::prelude::*;
+        proptest::collection::vec(
+            (
+                proptest::sample::select(&FUZZ_RECORD_NAMES[..]),
+                "[ -~]{0,74}",
+            ),
+            0..12,
+        )
+        .prop_map(|lines| {
+            lines
+                .into_iter()
+                .map(|(name, rest)| format!("{}{}\n", name, rest))
+                .collect()
+        })
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn random_bytes_never_panic(input in proptest::collection::vec(proptest::num::u8::ANY, 0..512)) {
+            let _ = try_parse_with_options(&String::from_utf8_lossy(&input), &ParseOptions::default());
+        }
+
+        #[test]
+        fn random_records_never_panic(input in fuzz_lines()) {
+            let _ = try_parse_with_options(&input, &ParseOptions::default());
+        }
+    }
 }
@@ -1,11 +1,557 @@
 use super::{ast::types::*, primitive::*};
-use nom::character::complete::line_ending;
-use nom::{do_parse, named};
+use nom::character::complete::{line_ending, space0};
+use nom::number::complete::double;
+use nom::{alt, do_parse, many1, map, named, tag};
 
 named!(
     pub remark_record_parser<Record>,
     do_parse!(
         remark
+        >> rest: till_line_ending
+        >> line_ending
+        >> (Record::Remark(String::from_utf8_lossy(rest).trim_end().to_owned()))));
+
+named!(
+    #[doc=r#"Parses the resolution line of REMARK 2, such as `REMARK   2 RESOLUTION.    2.10 ANGSTROMS.`
+If successfull returns [Record](../ast/types/enum.Record.html) variant containing a
+[Resolution](../ast/types/struct.Resolution.html) instance, empty for `NOT APPLICABLE` resolutions.
+"#],
+    pub remark2_record_parser<Record>,
+    do_parse!(
+        remark
+        >> tag!("   2 RESOLUTION.")
+        >> space0
+        >> angstroms: alt!(map!(tag!("NOT APPLICABLE"), |_| None) | map!(double, Some))
         >> till_line_ending
         >> line_ending
-        >> (Record::Remark)));
+        >> (Record::Resolution(Resolution { angstroms }))));
+
+named!(
+    remark3_line_parser<String>,
+    do_parse!(
+        remark
+        >> tag!("   3")
+        >> rest: till_line_ending
+        >> line_ending
+        >> (String::from_utf8_lossy(rest).into_owned()))
+);
+
+/// value of a `KEY : VALUE` line of REMARK 3 if its key, with spaces collapsed, is `key`
+fn remark3_value(line: &str, key: &str) -> Option<Option<f64>> {
+    let (k, v) = line.split_once(':')?;
+    if k.split_whitespace().eq(key.split_whitespace()) {
+        Some(v.trim().parse().ok())
+    } else {
+        None
+    }
+}
+
+named!(
+#[doc=r#"Parses a REMARK 3 block describing the refinement, extracting its R value and free R value.
+R value of the working set is preferred, the one of working and test sets together is used for
+entries not giving it. Values given as `NULL` are left empty. If successfull returns
+[Record](../ast/types/enum.Record.html) variant containing a [RefinementStats](../ast/types/struct.RefinementStats.html)
+instance.
+"#],
+pub remark3_record_parser<Record>,
+map!(many1!(remark3_line_parser), |lines: Vec<String>| {
+    let find = |key: &str| lines.iter().find_map(|l| remark3_value(l, key)).flatten();
+    Record::RefinementStats(RefinementStats {
+        r_value: find("R VALUE (WORKING SET)").or_else(|| find("R VALUE (WORKING + TEST SET)")),
+        r_free: find("FREE R VALUE"),
+    })
+}));
+
+named!(
+    remark4_line_parser<String>,
+    do_parse!(
+        remark
+        >> tag!("   4")
+        >> rest: till_line_ending
+        >> line_ending
+        >> (String::from_utf8_lossy(rest).into_owned()))
+);
+
+/// version number following `COMPLIES WITH FORMAT V.` in a REMARK 4 line
+fn format_version(line: &str) -> Option<String> {
+    let (_, rest) = line.split_once("COMPLIES WITH FORMAT V.")?;
+    rest.split(|c: char| c == ',' || c.is_whitespace())
+        .find(|v| !v.is_empty())
+        .map(String::from)
+}
+
+named!(
+#[doc=r#"Parses a REMARK 4 block, such as `REMARK   4 1ABC COMPLIES WITH FORMAT V. 3.30, 13-JUL-11`,
+stating the version of the PDB format the entry complies with. If successfull returns
+[Record](../ast/types/enum.Record.html) variant containing a [FormatVersion](../ast/types/struct.FormatVersion.html)
+instance.
+"#],
+pub remark4_record_parser<Record>,
+map!(many1!(remark4_line_parser), |lines: Vec<String>| {
+    Record::FormatVersion(FormatVersion {
+        format_version: lines.iter().find_map(|l| format_version(l)),
+    })
+}));
+
+named!(
+    remark800_line_parser<String>,
+    do_parse!(
+        remark
+        >> tag!(" 800")
+        >> rest: till_line_ending
+        >> line_ending
+        >> (String::from_utf8_lossy(rest).trim().to_owned()))
+);
+
+/// Groups `KEY: VALUE` lines of REMARK 800 into site descriptions, each starting at its
+/// SITE_IDENTIFIER line. Lines without a known key continue the description.
+fn site_descriptions(lines: &[String]) -> Vec<SiteDescription> {
+    let mut sites: Vec<SiteDescription> = Vec::new();
+    for line in lines {
+        let (key, value) = line
+            .split_once(':')
+            .map_or(("", line.as_str()), |(k, v)| (k.trim(), v.trim()));
+        match (key, sites.last_mut()) {
+            ("SITE_IDENTIFIER", _) => sites.push(SiteDescription {
+                id: value.to_owned(),
+                ..Default::default()
+            }),
+            ("EVIDENCE_CODE", Some(site)) => site.evidence = value.to_owned(),
+            ("SITE_DESCRIPTION", Some(site)) => site.description = value.to_owned(),
+            (_, Some(site)) if !site.description.is_empty() && !line.is_empty() => {
+                site.description.push(' ');
+                site.description.push_str(line);
+            }
+            _ => {}
+        }
+    }
+    sites
+}
+
+named!(
+#[doc=r#"Parses a REMARK 800 block describing the sites listed in SITE records. If successfull returns
+[Record](../ast/types/enum.Record.html) variant containing a [SiteDescription](../ast/types/struct.SiteDescription.html)
+for each SITE_IDENTIFIER of the block.
+"#],
+pub remark800_record_parser<Record>,
+map!(many1!(remark800_line_parser), |lines: Vec<String>| {
+    Record::SiteDescriptions(site_descriptions(&lines))
+}));
+
+named!(
+    remark465_line_parser<String>,
+    do_parse!(
+        remark
+        >> tag!(" 465")
+        >> rest: till_line_ending
+        >> line_ending
+        >> (String::from_utf8_lossy(rest).trim_end().to_owned()))
+);
+
+/// Missing residue of a REMARK 465 line, columns of `line` counted from column 11. Lines
+/// of the explanatory text and the column headings are not residues and yield `None`.
+///
+/// | COLUMNS  | DATA  TYPE    | FIELD       | DEFINITION                        |
+/// |----------|---------------|-------------|-----------------------------------|
+/// | 12 - 14  | Integer       | model       | Model number, blank for all       |
+/// | 16 - 18  | Residue name  | resName     | Residue name.                     |
+/// | 20       | Character     | chainID     | Chain identifier.                 |
+/// | 22 - 26  | Integer       | seqNum      | Residue sequence number.          |
+/// | 27       | AChar         | iCode       | Insertion code.                   |
+fn missing_residue(line: &str) -> Option<MissingResidue> {
+    let field = |from: usize, to: usize| line.get(from.min(line.len())..to.min(line.len()));
+    let model = field(0, 4)?.trim();
+    let residue_name = field(5, 8)?.trim();
+    if residue_name.is_empty() || !residue_name.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    Some(MissingResidue {
+        model: if model.is_empty() {
+            None
+        } else {
+            Some(model.parse().ok()?)
+        },
+        residue_name: residue_name.to_owned(),
+        residue_id: ResidueId {
+            chain_id: field(9, 10)?.chars().next().unwrap_or(' '),
+            residue_seq: field(11, 16)?.trim().parse().ok()?,
+            insertion_code: field(16, 17)
+                .and_then(|c| c.chars().next())
+                .filter(|c| *c != ' '),
+        },
+    })
+}
+
+named!(
+#[doc=r#"Parses a REMARK 465 block listing the residues which were not located in the experiment.
+If successfull returns [Record](../ast/types/enum.Record.html) variant containing a
+[MissingResidue](../ast/types/struct.MissingResidue.html) for each residue line of the block.
+"#],
+pub remark465_record_parser<Record>,
+map!(many1!(remark465_line_parser), |lines: Vec<String>| {
+    Record::MissingResidues(lines.iter().filter_map(|l| missing_residue(l)).collect())
+}));
+
+named!(
+    remark350_line_parser<String>,
+    do_parse!(
+        remark
+        >> tag!(" 350")
+        >> rest: till_line_ending
+        >> line_ending
+        >> (String::from_utf8_lossy(rest).trim().to_owned()))
+);
+
+/// serial, matrix row and translation element of a `BIOMTn` line, numbered from 0
+fn biomt_row(line: &str) -> Option<(usize, u32, [f64; 3], f64)> {
+    let line = line.strip_prefix("BIOMT")?;
+    let row = line.get(..1)?.parse::<usize>().ok()?.checked_sub(1)?;
+    let mut values = line[1..].split_whitespace();
+    let serial = values.next()?.parse().ok()?;
+    let mut numbers = values.map(|v| v.parse::<f64>().ok());
+    let mut next = || numbers.next().flatten();
+    Some((row, serial, [next()?, next()?, next()?], next()?))
+}
+
+/// Groups REMARK 350 lines into assemblies, each starting at its BIOMOLECULE line. Every
+/// APPLY THE FOLLOWING TO CHAINS line starts a group of the assembly, taking the chains of
+/// the AND CHAINS lines continuing it and the BIOMT operators following it.
+fn biological_assemblies(lines: &[String]) -> Vec<BiologicalAssembly> {
+    let mut assemblies: Vec<BiologicalAssembly> = Vec::new();
+    for line in lines {
+        let (key, value) = line
+            .split_once(':')
+            .map_or((line.as_str(), ""), |(k, v)| (k.trim(), v.trim()));
+        let chains = || {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .map(String::from)
+        };
+        match (key, assemblies.last_mut()) {
+            ("BIOMOLECULE", _) => assemblies.push(BiologicalAssembly {
+                id: value.parse().unwrap_or_default(),
+                ..Default::default()
+            }),
+            ("APPLY THE FOLLOWING TO CHAINS", Some(assembly)) => {
+                assembly.groups.push(AssemblyGroup {
+                    chains: chains().collect(),
+                    ..Default::default()
+                })
+            }
+            ("AND CHAINS", Some(assembly)) => {
+                if let Some(group) = assembly.groups.last_mut() {
+                    group.chains.extend(chains())
+                }
+            }
+            (_, Some(assembly)) => {
+                if let Some((row, serial, matrix_row, translation)) = biomt_row(line) {
+                    if assembly.groups.is_empty() {
+                        assembly.groups.push(AssemblyGroup::default());
+                    }
+                    let operators = &mut assembly.groups.last_mut().unwrap().operators;
+                    if row == 0 {
+                        operators.push(Biomt {
+                            serial,
+                            ..Default::default()
+                        });
+                    }
+                    if let Some(biomt) = operators.last_mut().filter(|_| row < 3) {
+                        biomt.matrix[row] = matrix_row;
+                        biomt.translation[row] = translation;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    assemblies
+}
+
+named!(
+#[doc=r#"Parses a REMARK 350 block describing the biological assemblies of the entry. If successfull
+returns [Record](../ast/types/enum.Record.html) variant containing a [BiologicalAssembly](../ast/types/struct.BiologicalAssembly.html)
+for each BIOMOLECULE of the block, with the chains it applies to and its BIOMT operators.
+"#],
+pub remark350_record_parser<Record>,
+map!(many1!(remark350_line_parser), |lines: Vec<String>| {
+    Record::BiologicalAssemblies(biological_assemblies(&lines))
+}));
+
+/// Coordinates of the biological assembly built by applying the operators of each group of
+/// `assembly` to the atoms of the chains of that group. Copies are given group by group and
+/// operator by operator, each holding the selected atoms in their original order.
+pub fn generate_assembly(atoms: &[Atom], assembly: &BiologicalAssembly) -> Vec<Atom> {
+    assembly
+        .groups
+        .iter()
+        .flat_map(|group| {
+            let selected = atoms
+                .iter()
+                .filter(|a| {
+                    group
+                        .chains
+                        .iter()
+                        .any(|c| c.chars().eq(std::iter::once(a.chain_id)))
+                })
+                .collect::<Vec<_>>();
+            group.operators.iter().flat_map(move |op| {
+                selected
+                    .iter()
+                    .map(|a| {
+                        let position = [a.x, a.y, a.z];
+                        let [x, y, z] = [0, 1, 2].map(|row| {
+                            op.matrix[row]
+                                .iter()
+                                .zip(position.iter())
+                                .map(|(m, p)| m * p)
+                                .sum::<f64>()
+                                + op.translation[row]
+                        });
+                        Atom {
+                            x,
+                            y,
+                            z,
+                            ..(*a).clone()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn missing_residues() {
+        match remark465_record_parser(
+            r#"REMARK 465
+REMARK 465 MISSING RESIDUES
+REMARK 465 THE FOLLOWING RESIDUES WERE NOT LOCATED IN THE
+REMARK 465 EXPERIMENT. (M=MODEL NUMBER; RES=RESIDUE NAME; C=CHAIN
+REMARK 465 IDENTIFIER; SSSEQ=SEQUENCE NUMBER; I=INSERTION CODE.)
+REMARK 465
+REMARK 465   M RES C SSSEQI
+REMARK 465     MET A     1
+REMARK 465   2 GLY B    47A
+"#
+            .as_bytes(),
+        ) {
+            Ok((_, Record::MissingResidues(residues))) => assert_eq!(
+                residues,
+                vec![
+                    MissingResidue {
+                        model: None,
+                        residue_name: "MET".to_owned(),
+                        residue_id: ResidueId {
+                            chain_id: 'A',
+                            residue_seq: 1,
+                            insertion_code: None
+                        }
+                    },
+                    MissingResidue {
+                        model: Some(2),
+                        residue_name: "GLY".to_owned(),
+                        residue_id: ResidueId {
+                            chain_id: 'B',
+                            residue_seq: 47,
+                            insertion_code: Some('A')
+                        }
+                    }
+                ]
+            ),
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn refinement_stats() {
+        match remark3_record_parser(
+            r#"REMARK   3
+REMARK   3 REFINEMENT.
+REMARK   3   PROGRAM     : REFMAC 5.2.0019
+REMARK   3  FIT TO DATA USED IN REFINEMENT.
+REMARK   3   CROSS-VALIDATION METHOD          : THROUGHOUT
+REMARK   3   R VALUE     (WORKING + TEST SET) : 0.19710
+REMARK   3   R VALUE            (WORKING SET) : 0.19452
+REMARK   3   FREE R VALUE                     : 0.24621
+REMARK   3   FREE R VALUE TEST SET SIZE   (%) : 5.1
+REMARK   4
+"#
+            .as_bytes(),
+        ) {
+            Ok((rest, Record::RefinementStats(r))) => {
+                assert_eq!(r.r_value, Some(0.19452));
+                assert_eq!(r.r_free, Some(0.24621));
+                assert!(rest.starts_with(b"REMARK   4"));
+            }
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn site_description() {
+        match remark800_record_parser(
+            r#"REMARK 800
+REMARK 800 SITE
+REMARK 800 SITE_IDENTIFIER: AC1
+REMARK 800 EVIDENCE_CODE: SOFTWARE
+REMARK 800 SITE_DESCRIPTION: BINDING SITE FOR RESIDUE ZN A 301
+REMARK 800 SITE_IDENTIFIER: CAT
+REMARK 800 EVIDENCE_CODE: AUTHOR
+REMARK 800 SITE_DESCRIPTION: CATALYTIC TRIAD OF THE
+REMARK 800 SERINE PROTEASE
+"#
+            .as_bytes(),
+        ) {
+            Ok((_, Record::SiteDescriptions(sites))) => {
+                assert_eq!(sites.len(), 2);
+                assert_eq!(
+                    sites[1],
+                    SiteDescription {
+                        id: "CAT".to_owned(),
+                        evidence: "AUTHOR".to_owned(),
+                        description: "CATALYTIC TRIAD OF THE SERINE PROTEASE".to_owned(),
+                    }
+                );
+            }
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn biological_assembly() {
+        match remark350_record_parser(
+            r#"REMARK 350 COORDINATES FOR A COMPLETE MULTIMER REPRESENTING THE KNOWN
+REMARK 350 BIOLOGICALLY SIGNIFICANT OLIGOMERIZATION STATE OF THE
+REMARK 350 MOLECULE CAN BE GENERATED BY APPLYING BIOMT TRANSFORMATIONS
+REMARK 350 GIVEN BELOW.  BOTH NON-CRYSTALLOGRAPHIC AND
+REMARK 350 CRYSTALLOGRAPHIC OPERATIONS ARE GIVEN.
+REMARK 350
+REMARK 350 BIOMOLECULE: 1
+REMARK 350 AUTHOR DETERMINED BIOLOGICAL UNIT: DIMERIC
+REMARK 350 APPLY THE FOLLOWING TO CHAINS: A,
+REMARK 350                    AND CHAINS: B
+REMARK 350   BIOMT1   1  1.000000  0.000000  0.000000        0.00000
+REMARK 350   BIOMT2   1  0.000000  1.000000  0.000000        0.00000
+REMARK 350   BIOMT3   1  0.000000  0.000000  1.000000        0.00000
+REMARK 350   BIOMT1   2 -1.000000  0.000000  0.000000       52.00000
+REMARK 350   BIOMT2   2  0.000000  1.000000  0.000000        0.00000
+REMARK 350   BIOMT3   2  0.000000  0.000000 -1.000000       30.95000
+REMARK 350 APPLY THE FOLLOWING TO CHAINS: C
+REMARK 350   BIOMT1   3  1.000000  0.000000  0.000000        0.00000
+REMARK 350   BIOMT2   3  0.000000  1.000000  0.000000        0.00000
+REMARK 350   BIOMT3   3  0.000000  0.000000  1.000000        0.00000
+REMARK 465
+"#
+            .as_bytes(),
+        ) {
+            Ok((rest, Record::BiologicalAssemblies(assemblies))) => {
+                assert_eq!(assemblies.len(), 1);
+                assert_eq!(assemblies[0].id, 1);
+                let groups = &assemblies[0].groups;
+                assert_eq!(groups.len(), 2);
+                assert_eq!(groups[0].chains, vec!["A", "B"]);
+                assert_eq!(groups[0].operators.len(), 2);
+                assert_eq!(groups[0].operators[1].serial, 2);
+                assert_eq!(groups[0].operators[1].matrix[2], [0.0, 0.0, -1.0]);
+                assert_eq!(groups[0].operators[1].translation, [52.0, 0.0, 30.95]);
+                assert_eq!(groups[1].chains, vec!["C"]);
+                assert_eq!(groups[1].operators.len(), 1);
+                assert_eq!(groups[1].operators[0].serial, 3);
+                assert!(rest.starts_with(b"REMARK 465"));
+            }
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn format_version() {
+        match remark4_record_parser(
+            r#"REMARK   4
+REMARK   4 1ABC COMPLIES WITH FORMAT V. 3.30, 13-JUL-11
+REMARK 100
+"#
+            .as_bytes(),
+        ) {
+            Ok((rest, Record::FormatVersion(v))) => {
+                assert_eq!(v.format_version, Some("3.30".to_owned()));
+                assert!(rest.starts_with(b"REMARK 100"));
+            }
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn resolution() {
+        match remark2_record_parser("REMARK   2 RESOLUTION.    2.10 ANGSTROMS.\n".as_bytes()) {
+            Ok((_, Record::Resolution(r))) => assert_eq!(r.angstroms, Some(2.1)),
+            e => panic!("{:?}", e),
+        }
+        match remark2_record_parser("REMARK   2 RESOLUTION. NOT APPLICABLE.\n".as_bytes()) {
+            Ok((_, Record::Resolution(r))) => assert_eq!(r.angstroms, None),
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn assembly_generation() {
+        let atom = |chain_id, x, y, z| Atom {
+            chain_id,
+            x,
+            y,
+            z,
+            ..Default::default()
+        };
+        let atoms = vec![
+            atom('A', 1.0, 2.0, 3.0),
+            atom('A', -1.0, 0.5, 0.0),
+            atom('B', 9.0, 9.0, 9.0),
+        ];
+        let identity = Biomt {
+            serial: 1,
+            matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            translation: [0.0, 0.0, 0.0],
+        };
+        let assembly = BiologicalAssembly {
+            id: 1,
+            groups: vec![
+                AssemblyGroup {
+                    chains: vec!["A".to_owned()],
+                    operators: vec![
+                        identity.clone(),
+                        Biomt {
+                            serial: 2,
+                            matrix: [[-1.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, 1.0]],
+                            translation: [10.0, 0.0, 0.0],
+                        },
+                    ],
+                },
+                AssemblyGroup {
+                    chains: vec!["B".to_owned()],
+                    operators: vec![identity],
+                },
+            ],
+        };
+        let generated = generate_assembly(&atoms, &assembly);
+        assert_eq!(generated.len(), 5);
+        assert_eq!(
+            (generated[0].x, generated[0].y, generated[0].z),
+            (1.0, 2.0, 3.0)
+        );
+        assert_eq!(
+            (generated[2].x, generated[2].y, generated[2].z),
+            (9.0, -2.0, 3.0)
+        );
+        assert_eq!((generated[3].x, generated[3].y), (11.0, -0.5));
+        assert_eq!(
+            (generated[4].chain_id, generated[4].x, generated[4].y),
+            ('B', 9.0, 9.0)
+        );
+        assert!(generated[..4].iter().all(|a| a.chain_id == 'A'));
+    }
+}
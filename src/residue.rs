@@ -0,0 +1,206 @@
+/*!
+Contains the mapping between residue names used in PDB records and one letter codes. Covers standard
+amino acids, DNA and RNA nucleotides and commonly observed modified amino acids, which are mapped to
+the one letter code of their parent residue.
+*/
+use super::ast::types::PolymerType;
+
+const STANDARD_AMINO_ACIDS: [(&str, char); 22] = [
+    ("ALA", 'A'),
+    ("ARG", 'R'),
+    ("ASN", 'N'),
+    ("ASP", 'D'),
+    ("CYS", 'C'),
+    ("GLN", 'Q'),
+    ("GLU", 'E'),
+    ("GLY", 'G'),
+    ("HIS", 'H'),
+    ("ILE", 'I'),
+    ("LEU", 'L'),
+    ("LYS", 'K'),
+    ("MET", 'M'),
+    ("PHE", 'F'),
+    ("PRO", 'P'),
+    ("SER", 'S'),
+    ("THR", 'T'),
+    ("TRP", 'W'),
+    ("TYR", 'Y'),
+    ("VAL", 'V'),
+    ("SEC", 'U'),
+    ("PYL", 'O'),
+];
+
+const NUCLEOTIDES: [(&str, char); 10] = [
+    ("A", 'A'),
+    ("C", 'C'),
+    ("G", 'G'),
+    ("U", 'U'),
+    ("I", 'I'),
+    ("DA", 'A'),
+    ("DC", 'C'),
+    ("DG", 'G'),
+    ("DT", 'T'),
+    ("DI", 'I'),
+];
+
+const MODIFIED_AMINO_ACIDS: [(&str, char); 12] = [
+    ("MSE", 'M'),
+    ("HYP", 'P'),
+    ("MLY", 'K'),
+    ("M3L", 'K'),
+    ("SEP", 'S'),
+    ("TPO", 'T'),
+    ("PTR", 'Y'),
+    ("CSO", 'C'),
+    ("CME", 'C'),
+    ("KCX", 'K'),
+    ("PCA", 'E'),
+    ("ASX", 'B'),
+];
+
+/// Converts a residue name such as `ALA` or `DA` to its one letter code.
+/// Returns `None` for unknown residue names.
+/// # Example
+/// ```
+/// # use patoz::residue::three_to_one;
+/// assert_eq!(three_to_one("TRP"), Some('W'));
+/// assert_eq!(three_to_one("HOH"), None);
+/// ```
+pub fn three_to_one(code: &str) -> Option<char> {
+    let code = code.trim();
+    STANDARD_AMINO_ACIDS
+        .iter()
+        .chain(NUCLEOTIDES.iter())
+        .chain(MODIFIED_AMINO_ACIDS.iter())
+        .find(|(name, _)| *name == code)
+        .map(|(_, one)| *one)
+}
+
+/// residue names used for water molecules
+const WATER: [&str; 2] = ["HOH", "WAT"];
+
+/// Returns true if `name` is a residue name used for water
+/// # Example
+/// ```
+/// # use patoz::residue::is_water;
+/// assert!(is_water("HOH"));
+/// assert!(!is_water("HEM"));
+/// ```
+pub fn is_water(name: &str) -> bool {
+    WATER.contains(&name.trim())
+}
+
+/// Converts a one letter amino acid code to its standard three letter residue name.
+/// Nucleotides share one letter codes with amino acids, so they are not covered.
+pub fn one_to_three(code: char) -> Option<&'static str> {
+    STANDARD_AMINO_ACIDS
+        .iter()
+        .find(|(_, one)| *one == code)
+        .map(|(name, _)| *name)
+}
+
+/// Converts residue names of a chain to a one letter sequence, such as the sequence
+/// of a FASTA record. Modified and unknown residues are written as `X`.
+/// # Example
+/// ```
+/// # use patoz::residue::to_one_letter_sequence;
+/// let residues = vec!["MET".to_owned(), "MSE".to_owned(), "HOH".to_owned()];
+/// assert_eq!(to_one_letter_sequence(&residues), "MXX");
+/// ```
+pub fn to_one_letter_sequence(residues: &[String]) -> String {
+    residues
+        .iter()
+        .map(|r| {
+            let name = r.trim();
+            STANDARD_AMINO_ACIDS
+                .iter()
+                .chain(NUCLEOTIDES.iter())
+                .find(|(n, _)| *n == name)
+                .map_or('X', |(_, one)| *one)
+        })
+        .collect()
+}
+
+/// Detects the polymer type of a chain from its SEQRES or ATOM residue names. The type
+/// covering more than half of the residues is returned, residue names belonging to none
+/// of the tables, such as ligands or waters, count against every type.
+/// # Example
+/// ```
+/// # use patoz::{residue::polymer_type, PolymerType};
+/// let residues = vec!["DA".to_owned(), "DT".to_owned(), "DG".to_owned()];
+/// assert_eq!(polymer_type(&residues), PolymerType::Dna);
+/// ```
+pub fn polymer_type(residues: &[String]) -> PolymerType {
+    let count = |table: &[(&str, char)]| {
+        residues
+            .iter()
+            .filter(|r| table.iter().any(|(name, _)| *name == r.trim()))
+            .count()
+    };
+    let protein = count(&STANDARD_AMINO_ACIDS) + count(&MODIFIED_AMINO_ACIDS);
+    let dna = count(&NUCLEOTIDES[5..]);
+    let rna = count(&NUCLEOTIDES[..5]);
+    [
+        (protein, PolymerType::Protein),
+        (dna, PolymerType::Dna),
+        (rna, PolymerType::Rna),
+    ]
+    .iter()
+    .find(|(n, _)| *n * 2 > residues.len())
+    .map_or(PolymerType::Unknown, |(_, t)| *t)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn amino_acid() {
+        assert_eq!(three_to_one("ALA"), Some('A'));
+        assert_eq!(three_to_one("MSE"), Some('M'));
+        assert_eq!(one_to_three('A'), Some("ALA"));
+    }
+
+    #[test]
+    fn nucleotide() {
+        assert_eq!(three_to_one("DA"), Some('A'));
+        assert_eq!(three_to_one("U"), Some('U'));
+    }
+
+    fn names(residues: &[&str]) -> Vec<String> {
+        residues.iter().map(|r| (*r).to_owned()).collect()
+    }
+
+    #[test]
+    fn one_letter_sequence() {
+        assert_eq!(
+            to_one_letter_sequence(&names(&["GLY", "SER", "HIS", "MET", "TRP"])),
+            "GSHMW"
+        );
+        assert_eq!(
+            to_one_letter_sequence(&names(&["ALA", "PTR", "UNK"])),
+            "AXX"
+        );
+    }
+
+    #[test]
+    fn polymer() {
+        assert_eq!(
+            polymer_type(&names(&["MET", "ALA", "MSE", "GLY"])),
+            PolymerType::Protein
+        );
+        assert_eq!(
+            polymer_type(&names(&["A", "U", "G", "C"])),
+            PolymerType::Rna
+        );
+        assert_eq!(polymer_type(&names(&["DA", "DT", "DC"])), PolymerType::Dna);
+        assert_eq!(polymer_type(&names(&["HOH", "HOH"])), PolymerType::Unknown);
+        assert_eq!(polymer_type(&[]), PolymerType::Unknown);
+    }
+
+    #[test]
+    fn unknown() {
+        assert_eq!(three_to_one("XYZ"), None);
+        assert_eq!(one_to_three('Z'), None);
+    }
+}
@@ -11,10 +11,8 @@ use nom::{
 
 use itertools::Itertools;
 
-use std::{str, str::FromStr};
-
 #[allow(dead_code)]
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct RevdatLine {
     modification_number: u32,
     continuation: u32,
@@ -32,8 +30,8 @@ named!(
             >> line_ending
             >> (RevdatLine {
                 modification_number,
-                continuation: if let Some(cc) = cont { cc } else { 0 },
-                rest: String::from_str(str::from_utf8(rest).unwrap()).unwrap(),
+                continuation: cont.unwrap_or_default(),
+                rest: String::from_utf8_lossy(rest).into_owned(),
             })
     )
 );
@@ -97,8 +95,8 @@ If successfull returns [Record](../ast/types/enum.Record.html) variant containin
                     }
                     _ => Revdat {
                         modification_number: 0,
-                        modification_date: chrono::naive::MIN_DATE,
-                        idcode: String::new(),
+                        modification_date: chrono::NaiveDate::MIN,
+                        idcode: IdCode::default(),
                         modification_type: ModificationType::InitialRelease,
                         modification_detail: Vec::new(),
                     },
@@ -114,10 +112,10 @@ named!(
         space0
             >> modification_date: date_parser
             >> space1
-            >> idcode: alphanum_word
+            >> idcode: idcode_parser
             >> space1
             >> modification_type: modification_type_parser
-            >> space1
+            >> space0
             >> modification_detail: idcode_list
             >> (Revdat {
                 modification_number: 0,
@@ -128,6 +126,20 @@ named!(
             })
     )
 );
+/// Compares the id code repeated on each modification of `revdats` with the id code of
+/// `header`. Returns a warning for each modification naming a different entry.
+pub fn check_revdat_idcodes(header: &Header, revdats: &Revdats) -> Vec<ParseWarning> {
+    revdats
+        .revdat
+        .iter()
+        .filter(|r| r.idcode != header.id_code)
+        .map(|r| ParseWarning::RevdatIdCodeMismatch {
+            modification_number: r.modification_number,
+            id_code: r.idcode.as_str().to_owned(),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
 
@@ -150,4 +162,87 @@ REVDAT   1   14-OCT-98 1BXO    0
             Err(_err) => assert!(false),
         }
     }
+
+    #[test]
+    fn revdat_sort() {
+        use crate::ast::types::{Record, Revdats};
+        use chrono::NaiveDate;
+
+        let res = super::revdat_record_parser(
+            r#"REVDAT   3   24-JAN-01 1BXO    3       ATOM                                     
+REVDAT   1   14-OCT-98 1BXO    0                                                                            
+REVDAT   2   22-DEC-99 1BXO    4       HEADER COMPND REMARK JRNL                
+"#
+            .as_bytes(),
+        );
+        if let Ok((
+            _,
+            Record::Revdats(Revdats {
+                revdat: mut revdats,
+            }),
+        )) = res
+        {
+            revdats.sort();
+            assert_eq!(
+                revdats
+                    .iter()
+                    .map(|r| (r.modification_number, r.modification_date))
+                    .collect::<Vec<_>>(),
+                vec![
+                    (1, NaiveDate::from_ymd_opt(1998, 10, 14).unwrap()),
+                    (2, NaiveDate::from_ymd_opt(1999, 12, 22).unwrap()),
+                    (3, NaiveDate::from_ymd_opt(2001, 1, 24).unwrap()),
+                ]
+            );
+        } else {
+            panic!();
+        }
+    }
+
+    #[test]
+    fn idcodes() {
+        use crate::ast::types::*;
+
+        let header = Header {
+            id_code: "1BXO".parse().unwrap(),
+            ..Default::default()
+        };
+        match super::revdat_record_parser(
+            r#"REVDAT   2   24-FEB-09 1BXP    1       VERSN                                    
+REVDAT   1   14-OCT-98 1BXO    0                                                
+"#
+            .as_bytes(),
+        ) {
+            Ok((_, Record::Revdats(revdats))) => assert_eq!(
+                super::check_revdat_idcodes(&header, &revdats),
+                vec![ParseWarning::RevdatIdCodeMismatch {
+                    modification_number: 2,
+                    id_code: "1BXP".to_owned()
+                }]
+            ),
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn trailing_spaces() {
+        use crate::ast::types::{Record, Revdats};
+
+        match super::revdat_record_parser(
+            format!(
+                "REVDAT   1   14-OCT-98 1BXO    0{}\nREVDAT   5   01-APR-03 1BXO    1       JRNL\n",
+                " ".repeat(80)
+            )
+            .as_bytes(),
+        ) {
+            Ok((_, Record::Revdats(Revdats { revdat }))) => {
+                assert_eq!(revdat.len(), 2);
+                assert_eq!(revdat[0].modification_number, 1);
+                assert!(revdat[0].modification_detail.is_empty());
+                assert_eq!(revdat[1].modification_number, 5);
+                assert_eq!(revdat[1].modification_detail, vec!["JRNL"]);
+            }
+            e => panic!("{:?}", e),
+        }
+    }
 }
@@ -1,8 +1,16 @@
 use super::{ast::types::*, primitive::*};
 use nom::{
     character::complete::{anychar, space1},
-    do_parse, named, opt, tag,
+    do_parse, map, named, opt, tag,
 };
+use std::collections::BTreeMap;
+
+named!(
+    #[doc=r#"Parses conflict comment of SEQADV records. Unknown comments are kept as
+[SeqAdvConflict::Other](../ast/types/enum.SeqAdvConflict.html)"#],
+    pub seqadv_conflict_parser<SeqAdvConflict>,
+    map!(alphanum_word_with_spaces_inside, |s: String| SeqAdvConflict::from(s.as_str()))
+);
 
 named!(
     pub seqadv_record_parser<Record>,
@@ -15,7 +23,7 @@ named!(
         >> space1
         >> chain_id : anychar
         >> tag!(" ")
-        >> sequence_number : fourdigit_integer
+        >> sequence_number : residue_seq_parser
         >> insertion_code : opt!(anychar)
         >> space1
         >> database : alphanum_word
@@ -26,7 +34,7 @@ named!(
         >> space1
         >> sequence_db_sequence_number : opt!(integer)
         >> space1
-        >> conflict : alphanum_word_with_spaces_inside
+        >> conflict : seqadv_conflict_parser
         >> till_line_ending
         >> (Record::Seqadv(Seqadv{
             idcode,
@@ -42,3 +50,74 @@ named!(
         }))
     )
 );
+
+/// Collects SEQADV records in `records` by chain identifier, keeping the order they were read
+pub fn seqadv_by_chain(records: &[Record]) -> BTreeMap<char, Vec<Seqadv>> {
+    let mut chains = BTreeMap::new();
+    for seqadv in records.iter().filter_map(|r| match r {
+        Record::Seqadv(s) => Some(s),
+        _ => None,
+    }) {
+        chains
+            .entry(seqadv.chain_id)
+            .or_insert_with(Vec::new)
+            .push(seqadv.clone());
+    }
+    chains
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn conflict() {
+        match seqadv_record_parser(
+            "SEQADV 1A0B ALA A   52  UNP  P00720    THR    52 ENGINEERED MUTATION            \n"
+                .as_bytes(),
+        ) {
+            Ok((_, Record::Seqadv(res))) => {
+                assert_eq!(res.sequence_db_residue, Some("THR".to_owned()));
+                assert_eq!(res.conflict, SeqAdvConflict::EngineeredMutation)
+            }
+            e => panic!("{:?}", e),
+        }
+        assert_eq!(
+            seqadv_conflict_parser("EXPRESSION TAG\n".as_bytes())
+                .unwrap()
+                .1,
+            SeqAdvConflict::ExpressionTag
+        );
+        assert_eq!(
+            seqadv_conflict_parser("SEE REMARK 999\n".as_bytes())
+                .unwrap()
+                .1,
+            SeqAdvConflict::SeeRemark999
+        );
+        assert_eq!(
+            seqadv_conflict_parser("LINKER\n".as_bytes()).unwrap().1,
+            SeqAdvConflict::Other("LINKER".to_owned())
+        );
+    }
+
+    #[test]
+    fn by_chain() {
+        let (_, pdb) = crate::parse(
+            r#"SEQADV 1A0B ALA A   52  UNP  P00720    THR    52 ENGINEERED MUTATION            
+SEQADV 1A0B SER B   38  UNP  P00720    CYS    38 ENGINEERED MUTATION            
+SEQADV 1A0B GLY A   97  UNP  P00720    CYS    97 ENGINEERED MUTATION            
+"#,
+        )
+        .unwrap();
+        let chains = seqadv_by_chain(pdb.records());
+        assert_eq!(chains.keys().collect::<Vec<_>>(), vec![&'A', &'B']);
+        assert_eq!(
+            chains[&'A']
+                .iter()
+                .map(|s| s.sequence_number)
+                .collect::<Vec<_>>(),
+            vec![52, 97]
+        );
+        assert_eq!(chains[&'B'][0].conflicting_residue, "SER");
+    }
+}
@@ -1,12 +1,9 @@
 use super::{ast::types::*, primitive::*};
-use nom::{
-    character::complete::{anychar, space1},
-    do_parse, many0, map, named, opt,
-};
-
-use itertools::Itertools;
+use nom::{character::complete::line_ending, do_parse, many0, named, take, verify};
+use std::collections::BTreeMap;
 
 #[allow(dead_code)]
+#[derive(Debug, Clone)]
 pub struct SeqresLine {
     serial_number: u32,
     chain_id: Option<char>,
@@ -43,13 +40,15 @@ named!(#[doc=r#"Parses a line of [SEQRES](http://www.wwpdb.org/documentation/fil
     pub seqres_line_parser<SeqresLine>,
     do_parse!(
         seqres
-            >> space1
-            >> serial_number: integer
-            >> space1
-            >> chain_id: opt!(anychar)
-            >> space1
-            >> num_res: integer
+            >> take!(1)
+            >> serial_number: threedigit_integer
+            >> take!(1)
+            >> chain_id: optional_char
+            >> take!(1)
+            >> num_res: fourdigit_integer
             >> residues: residue_list_parser
+            >> till_line_ending
+            >> line_ending
             >> (SeqresLine {
                 serial_number,
                 chain_id,
@@ -59,23 +58,153 @@ named!(#[doc=r#"Parses a line of [SEQRES](http://www.wwpdb.org/documentation/fil
     )
 );
 
-named!(seqres_parser<Vec<SeqresLine>>, many0!(seqres_line_parser));
-
 named!(
-    seqres_record_parser<Vec<Record>>,
-    map!(seqres_parser, |seqres: Vec<SeqresLine>| {
-        seqres
-            .into_iter()
-            .group_by(|a| a.chain_id)
-            .into_iter()
-            .map(|(k, v)| {
-                Record::Seqres(Seqres {
-                    chain_id: k,
-                    residues: v.fold(Vec::new(), |v: Vec<String>, sr: SeqresLine| {
-                        v.into_iter().chain(sr.residues).collect()
+    #[doc=r#"Parses consecutive SEQRES lines of a single chain. Residue names are not assumed
+to be amino acids, one and two character nucleotide names of DNA and RNA chains are read as well.
+If successfull returns [Record](../ast/types/enum.Record.html) variant containing
+[Seqres](../ast/types/struct.Seqres.html) instance.
+"#],
+    pub seqres_record_parser<Record>,
+    do_parse!(
+        first: seqres_line_parser
+            >> rest: many0!(verify!(seqres_line_parser, |l: &SeqresLine| l.chain_id
+                == first.chain_id))
+            >> (Record::Seqres(Seqres {
+                chain_id: first.chain_id,
+                num_res: first.num_res,
+                serial_numbers: std::iter::once(first.serial_number)
+                    .chain(rest.iter().map(|l| l.serial_number))
+                    .collect(),
+                residues: rest
+                    .into_iter()
+                    .fold(first.residues, |acc: Vec<String>, sr: SeqresLine| {
+                        acc.into_iter().chain(sr.residues).collect()
                     }),
-                })
-            })
-            .collect::<Vec<_>>()
-    })
+            }))
+    )
 );
+
+/// Checks serNum of the SEQRES lines folded into `seqres` start at 1 and increase by
+/// one each line. Returns a warning for each line whose serNum does not follow the
+/// previous line.
+pub fn check_serial_numbers(seqres: &Seqres) -> Vec<ParseWarning> {
+    let previous = std::iter::once(0).chain(seqres.serial_numbers.iter().copied());
+    seqres
+        .serial_numbers
+        .iter()
+        .zip(previous)
+        .filter(|(found, previous)| **found != previous + 1)
+        .map(|(found, previous)| ParseWarning::SeqresSerialOutOfOrder {
+            chain_id: seqres.chain_id,
+            expected: previous + 1,
+            found: *found,
+        })
+        .collect()
+}
+
+/// Collects residues of all SEQRES records in `records` by chain identifier, records
+/// without a chain identifier are keyed by a space. Residues of a chain split over separate
+/// records are appended in the order read.
+pub fn seqres_by_chain(records: &[Record]) -> BTreeMap<char, Vec<String>> {
+    let mut chains = BTreeMap::new();
+    for seqres in records.iter().filter_map(|r| match r {
+        Record::Seqres(s) => Some(s),
+        _ => None,
+    }) {
+        chains
+            .entry(seqres.chain_id.unwrap_or(' '))
+            .or_insert_with(Vec::new)
+            .extend(seqres.residues.iter().cloned());
+    }
+    chains
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rna() {
+        let res = seqres_record_parser(
+            r#"SEQRES   1 X   24    U   G   C   A   G   C   A   G   G   A   G   U   A          
+SEQRES   2 X   24    G   C   A   G   U   U   C   C   U   G   A                  
+"#
+            .as_bytes(),
+        );
+        if let Ok((_, Record::Seqres(seqres))) = res {
+            assert_eq!(seqres.chain_id, Some('X'));
+            assert_eq!(seqres.residues.len(), 24);
+            assert_eq!(seqres.residues[0], "U");
+            assert_eq!(seqres.residues[23], "A");
+        } else {
+            panic!("{:?}", res);
+        }
+    }
+
+    #[test]
+    fn serial_numbers() {
+        let res = seqres_record_parser(
+            r#"SEQRES   1 A   26  MET ALA GLY ILE LEU VAL ALA GLY ILE LEU VAL ALA GLY          
+SEQRES   3 A   26  MET ALA GLY ILE LEU VAL ALA GLY ILE LEU VAL ALA GLY          
+SEQRES   4 A   26  MET ALA GLY ILE LEU VAL ALA GLY ILE LEU VAL ALA GLY          
+SEQRES   6 A   26  MET ALA                                                      
+"#
+            .as_bytes(),
+        );
+        if let Ok((_, Record::Seqres(seqres))) = res {
+            assert_eq!(seqres.serial_numbers, vec![1, 3, 4, 6]);
+            assert_eq!(
+                check_serial_numbers(&seqres),
+                vec![
+                    ParseWarning::SeqresSerialOutOfOrder {
+                        chain_id: Some('A'),
+                        expected: 2,
+                        found: 3
+                    },
+                    ParseWarning::SeqresSerialOutOfOrder {
+                        chain_id: Some('A'),
+                        expected: 5,
+                        found: 6
+                    }
+                ]
+            );
+        } else {
+            panic!("{:?}", res);
+        }
+    }
+
+    #[test]
+    fn chains() {
+        let input =
+            r#"SEQRES   1 A    3   DA  DT  DG                                                  
+SEQRES   1 B    2  GLY ILE                                                      
+"#
+            .as_bytes();
+        if let Ok((rest, Record::Seqres(seqres))) = seqres_record_parser(input) {
+            assert_eq!(seqres.residues, vec!["DA", "DT", "DG"]);
+            if let Ok((_, Record::Seqres(seqres))) = seqres_record_parser(rest) {
+                assert_eq!(seqres.chain_id, Some('B'));
+                assert_eq!(seqres.residues, vec!["GLY", "ILE"]);
+            } else {
+                panic!();
+            }
+        } else {
+            panic!();
+        }
+    }
+
+    #[test]
+    fn by_chain() {
+        let (_, pdb) = crate::parse(
+            r#"SEQRES   1 A    3  MET ALA GLY                                                  
+SEQRES   1 B    2  GLY ILE                                                      
+KEYWDS    HYDROLASE
+"#,
+        )
+        .unwrap();
+        let chains = seqres_by_chain(pdb.records());
+        assert_eq!(chains.len(), 2);
+        assert_eq!(chains[&'A'], vec!["MET", "ALA", "GLY"]);
+        assert_eq!(chains[&'B'], vec!["GLY", "ILE"]);
+    }
+}
@@ -0,0 +1,202 @@
+/*!
+Contains parsers related to [SHEET](http://www.wwpdb.org/documentation/file-format-content/format33/sect5.html#SHEET)
+records. SHEET records are used to identify the position of sheets in the molecule, along with
+[secondary_structure](fn.secondary_structure.html) assigning HELIX and SHEET ranges to residues.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{
+    character::complete::{anychar, line_ending},
+    do_parse, named, take, take_str,
+};
+use std::collections::BTreeMap;
+
+named!(
+    #[doc=r#"Parses a single SHEET record. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing a [Sheet](../ast/types/struct.Sheet.html) instance. Registration columns
+following the sense of the strand are not parsed.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE    | FIELD       | DEFINITION                                      |
+|----------|---------------|-------------|-------------------------------------------------|
+| 1 -  6   | Record name   | SHEET       |                                                 |
+| 8 - 10   | Integer       | strand      | Strand  number which starts at 1 for each       |
+|          |               |             | strand within a sheet and increases by one.     |
+| 12 - 14  | LString(3)    | sheetID     | Sheet  identifier.                              |
+| 15 - 16  | Integer       | numStrands  | Number  of strands in sheet.                    |
+| 18 - 20  | Residue name  | initResName | Residue  name of initial residue.               |
+| 22       | Character     | initChainID | Chain identifier of initial residue in strand.  |
+| 23 - 26  | Integer       | initSeqNum  | Sequence number of initial residue in strand.   |
+| 27       | AChar         | initICode   | Insertion code of initial residue in strand.    |
+| 29 - 31  | Residue name  | endResName  | Residue name of terminal residue.               |
+| 33       | Character     | endChainID  | Chain identifier of terminal residue.           |
+| 34 - 37  | Integer       | endSeqNum   | Sequence number of terminal residue.            |
+| 38       | AChar         | endICode    | Insertion code of terminal residue.             |
+| 39 - 40  | Integer       | sense       | Sense of strand with respect to previous        |
+|          |               |             | strand in the sheet. 0 if first strand,         |
+|          |               |             | 1 if parallel, -1 if anti-parallel.             |
+"#],
+    pub sheet_record_parser<Record>,
+    do_parse!(
+        sheet
+            >> take!(2)
+            >> strand: threedigit_integer
+            >> take!(1)
+            >> sheet_id: take_str!(3)
+            >> num_strands: twodigit_integer
+            >> take!(1)
+            >> init_residue_name: take_str!(3)
+            >> take!(1)
+            >> init_chain_id: anychar
+            >> init_residue_seq: residue_seq_parser
+            >> init_insertion_code: optional_char
+            >> take!(1)
+            >> end_residue_name: take_str!(3)
+            >> take!(1)
+            >> end_chain_id: anychar
+            >> end_residue_seq: residue_seq_parser
+            >> rest: till_line_ending
+            >> line_ending
+            >> (Record::Sheet(Sheet {
+                strand,
+                sheet_id: sheet_id.trim().to_owned(),
+                num_strands,
+                init_residue_name: init_residue_name.trim().to_owned(),
+                init_chain_id,
+                init_residue_seq,
+                init_insertion_code,
+                end_residue_name: end_residue_name.trim().to_owned(),
+                end_chain_id,
+                end_residue_seq,
+                end_insertion_code: text_field(rest, 0, 1).chars().next(),
+                sense: text_field(rest, 1, 3).parse().unwrap_or_default(),
+            }))
+    )
+);
+
+/// residues of the coordinate records in `records` along with their secondary structure.
+/// Residues within the range of a HELIX record are [SsType::Helix](../ast/types/enum.SsType.html),
+/// those within a SHEET strand are `Sheet` and the remaining ones are `Coil`. Ranges are
+/// compared by chain, residue sequence number and insertion code of both their initial and
+/// terminal residues.
+pub fn secondary_structure(records: &[Record]) -> BTreeMap<ResidueId, SsType> {
+    let ranges = records
+        .iter()
+        .filter_map(|r| match r {
+            Record::Helix(h) => Some((
+                ResidueId {
+                    chain_id: h.init_chain_id,
+                    residue_seq: h.init_residue_seq,
+                    insertion_code: h.init_insertion_code,
+                }..=ResidueId {
+                    chain_id: h.end_chain_id,
+                    residue_seq: h.end_residue_seq,
+                    insertion_code: h.end_insertion_code,
+                },
+                SsType::Helix,
+            )),
+            Record::Sheet(s) => Some((
+                ResidueId {
+                    chain_id: s.init_chain_id,
+                    residue_seq: s.init_residue_seq,
+                    insertion_code: s.init_insertion_code,
+                }..=ResidueId {
+                    chain_id: s.end_chain_id,
+                    residue_seq: s.end_residue_seq,
+                    insertion_code: s.end_insertion_code,
+                },
+                SsType::Sheet,
+            )),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    records
+        .iter()
+        .flat_map(|r| match r {
+            Record::Atom(a) => std::slice::from_ref(a),
+            Record::Model(m) => m.atoms.as_slice(),
+            _ => &[],
+        })
+        .map(|a| {
+            let id = a.residue_id();
+            let ss_type = ranges
+                .iter()
+                .find(|(range, _)| range.contains(&id))
+                .map_or(SsType::Coil, |(_, ss_type)| *ss_type);
+            (id, ss_type)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sheet() {
+        match sheet_record_parser(
+            "SHEET    2   A 5 LYS A  61  PHE A  66 -1  N  PHE A  66   O  ILE A  50          \n"
+                .as_bytes(),
+        ) {
+            Ok((_, Record::Sheet(res))) => {
+                assert_eq!(res.strand, 2);
+                assert_eq!(res.sheet_id, "A");
+                assert_eq!(res.num_strands, 5);
+                assert_eq!(res.init_residue_seq, 61);
+                assert_eq!(res.end_residue_name, "PHE");
+                assert_eq!(res.end_residue_seq, 66);
+                assert_eq!(res.sense, -1);
+            }
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn assignment() {
+        let (_, pdb) = crate::parse(
+            r#"HELIX    1  HA GLY A    2  GLY A    3  1                                   2
+SHEET    1   A 2 LYS A   4  PHE A   4  0
+ATOM      1  CA  GLY A   2      47.699  22.521  30.384  1.00 37.82           C
+ATOM      2  CA  GLY A   3      48.661  22.866  29.347  1.00 27.31           C
+ATOM      3  CA  LYS A   4      49.020  23.110  28.113  1.00 25.12           C
+ATOM      4  CA  ALA A   5      50.101  23.502  27.006  1.00 24.40           C
+"#,
+        )
+        .unwrap();
+        let assignment = secondary_structure(pdb.records());
+        let residue = |residue_seq| ResidueId {
+            chain_id: 'A',
+            residue_seq,
+            insertion_code: None,
+        };
+        assert_eq!(assignment.len(), 4);
+        assert_eq!(assignment[&residue(3)], SsType::Helix);
+        assert_eq!(assignment[&residue(4)], SsType::Sheet);
+        assert_eq!(assignment[&residue(5)], SsType::Coil);
+    }
+
+    #[test]
+    fn assignment_with_insertion_codes() {
+        let (_, pdb) = crate::parse(
+            r#"HELIX    1  HA GLY A   10A GLY A   12  1                                   3
+ATOM      1  CA  GLY A  10      47.699  22.521  30.384  1.00 37.82           C
+ATOM      2  CA  GLY A  10A     48.661  22.866  29.347  1.00 27.31           C
+ATOM      3  CA  GLY A  12      49.020  23.110  28.113  1.00 25.12           C
+ATOM      4  CA  GLY A  12A     50.101  23.502  27.006  1.00 24.40           C
+ATOM      5  CA  GLY B  11      51.101  23.502  27.006  1.00 24.40           C
+"#,
+        )
+        .unwrap();
+        let assignment = secondary_structure(pdb.records());
+        let residue = |chain_id, residue_seq, insertion_code| ResidueId {
+            chain_id,
+            residue_seq,
+            insertion_code,
+        };
+        assert_eq!(assignment[&residue('A', 10, None)], SsType::Coil);
+        assert_eq!(assignment[&residue('A', 10, Some('A'))], SsType::Helix);
+        assert_eq!(assignment[&residue('A', 12, None)], SsType::Helix);
+        assert_eq!(assignment[&residue('A', 12, Some('A'))], SsType::Coil);
+        assert_eq!(assignment[&residue('B', 11, None)], SsType::Coil);
+    }
+}
@@ -0,0 +1,116 @@
+/*!
+Contains parsers related to [SITE](http://www.wwpdb.org/documentation/file-format-content/format33/sect7.html#SITE)
+records. SITE records supply the identification of groups comprising important properties of the
+macromolecule, their biological meaning is described in REMARK 800.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{character::complete::line_ending, do_parse, many0, named, take, take_str, verify};
+
+#[derive(Debug, Clone)]
+struct SiteLine {
+    id: String,
+    num_residues: u32,
+    residues: Vec<(String, ResidueId)>,
+}
+
+named!(
+    site_line_parser<SiteLine>,
+    do_parse!(
+        site >> take!(3)
+            >> threedigit_integer
+            >> take!(1)
+            >> id: take_str!(3)
+            >> take!(1)
+            >> num_residues: twodigit_integer
+            >> rest: till_line_ending
+            >> line_ending
+            >> (SiteLine {
+                id: id.trim().to_owned(),
+                num_residues,
+                residues: (0..4)
+                    .filter_map(|i| {
+                        let start = 1 + 11 * i;
+                        let residue_name = text_field(rest, start, start + 3);
+                        let residue_seq = text_field(rest, start + 5, start + 9).parse().ok()?;
+                        Some((
+                            residue_name.to_owned(),
+                            ResidueId {
+                                chain_id: text_field(rest, start + 4, start + 5)
+                                    .chars()
+                                    .next()
+                                    .unwrap_or(' '),
+                                residue_seq,
+                                insertion_code: text_field(rest, start + 9, start + 10)
+                                    .chars()
+                                    .next(),
+                            },
+                        ))
+                    })
+                    .collect(),
+            })
+    )
+);
+
+named!(
+    #[doc=r#"Parses the SITE lines of a single site. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing a [Site](../ast/types/struct.Site.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE    | FIELD       | DEFINITION                                          |
+|----------|---------------|-------------|-----------------------------------------------------|
+| 1 -  6   | Record name   | SITE        |                                                     |
+| 8 - 10   | Integer       | seqNum      | Sequence number.                                    |
+| 12 - 14  | LString(3)    | siteID      | Site name.                                          |
+| 16 - 17  | Integer       | numRes      | Number of residues that compose the site.           |
+| 19 - 21  | Residue name  | resName1    | Residue name for first residue that creates the site|
+| 23       | Character     | chainID1    | Chain identifier for first residue of site.         |
+| 24 - 27  | Integer       | seq1        | Residue sequence number for first residue.          |
+| 28       | AChar         | iCode1      | Insertion code for first residue of the site.       |
+| 30 - 39  |               |             | Second residue, laid out as the first one           |
+| 41 - 50  |               |             | Third residue                                       |
+| 52 - 61  |               |             | Fourth residue                                      |
+"#],
+    pub site_record_parser<Record>,
+    do_parse!(
+        first: site_line_parser
+            >> rest: many0!(verify!(site_line_parser, |l: &SiteLine| l.id == first.id))
+            >> (Record::Site(Site {
+                id: first.id.clone(),
+                num_residues: first.num_residues,
+                residues: first
+                    .residues
+                    .iter()
+                    .chain(rest.iter().flat_map(|l| l.residues.iter()))
+                    .cloned()
+                    .collect(),
+            }))
+    )
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn site() {
+        match site_record_parser(
+            r#"SITE     1 AC1  5 HIS A  94  HIS A  96  HIS A 119  THR A 199
+SITE     2 AC1  5 HOH A 301
+SITE     1 AC2  1 GLU A 106
+"#
+            .as_bytes(),
+        ) {
+            Ok((rest, Record::Site(res))) => {
+                assert_eq!(res.id, "AC1");
+                assert_eq!(res.num_residues, 5);
+                assert_eq!(res.residues.len(), 5);
+                assert_eq!(res.residues[1].0, "HIS");
+                assert_eq!(res.residues[1].1.residue_seq, 96);
+                assert_eq!(res.residues[4].0, "HOH");
+                assert!(rest.starts_with(b"SITE     1 AC2"));
+            }
+            e => panic!("{:?}", e),
+        }
+    }
+}
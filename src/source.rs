@@ -5,13 +5,16 @@ The SOURCE record specifies the biological or chemical source of each molecule i
 use super::{ast::types::*, primitive::*};
 use nom::{
     character::complete::{line_ending, space0, space1},
-    do_parse, fold_many1, map, named, opt,
+    do_parse, map_opt, named, opt,
 };
 
 use super::compnd::tokens_parser;
-use crate::make_line_folder;
+use crate::{
+    make_line_folder,
+    writer::{continued_record_lines, token_texts},
+};
 
-use std::{marker::PhantomData, str, str::FromStr};
+use std::{marker::PhantomData, str};
 
 #[allow(dead_code)]
 struct SourceLine;
@@ -26,8 +29,9 @@ named!(
             >> rest: till_line_ending
             >> line_ending
             >> (Continuation::<SourceLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
-                remaining: String::from_str(str::from_utf8(rest).unwrap()).unwrap(),
+                continuation: cont.unwrap_or_default(),
+                remaining: String::from_utf8_lossy(rest).into_owned(),
+                line_number: None,
                 phantom: PhantomData,
             })
     )
@@ -49,14 +53,99 @@ Record layout :
 |           | List           |               | macromolecule in a  token: value format. |
     "#],
     pub source_token_parser<Record>,
-    map!(
+    map_opt!(
         source_line_folder,
-        |v: Vec<u8>| tokens_parser(v.as_slice()).map(|res| Record::Source(Source{tokens : res.1})).expect("Can not parse source record")
+        |v: Vec<u8>| tokens_parser(v.as_slice()).map(|res| Record::Source(Source{tokens : res.1})).ok()
     )
 );
 
+/// Builds a [Source](../ast/types/struct.Source.html) token by token, in the order
+/// the methods are called. Tokens of a molecule follow its `mol_id`.
+#[derive(Debug, Clone, Default)]
+pub struct SourceBuilder {
+    tokens: Vec<Token>,
+}
+
+impl SourceBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// appends a `MOL_ID` token starting a new molecule
+    pub fn mol_id(mut self, id: u32) -> Self {
+        self.tokens.push(Token::MoleculeId(id));
+        self
+    }
+
+    /// appends an `ORGANISM_SCIENTIFIC` token
+    pub fn organism_scientific(mut self, organism: &str) -> Self {
+        self.tokens
+            .push(Token::OrganismScientific(organism.to_owned()));
+        self
+    }
+
+    /// appends an `ORGANISM_TAXID` token
+    pub fn organism_taxid(mut self, id: u32) -> Self {
+        self.tokens.push(Token::OrganismTaxId { id: vec![id] });
+        self
+    }
+
+    /// appends an `EXPRESSION_SYSTEM` token
+    pub fn expression_system(mut self, system: &str) -> Self {
+        self.tokens.push(Token::ExpressionSystem(system.to_owned()));
+        self
+    }
+
+    /// appends an `EXPRESSION_SYSTEM_TAXID` token
+    pub fn expression_system_taxid(mut self, id: u32) -> Self {
+        self.tokens
+            .push(Token::ExpressionSystemTaxId { id: vec![id] });
+        self
+    }
+
+    /// appends any other token
+    pub fn token(mut self, token: Token) -> Self {
+        self.tokens.push(token);
+        self
+    }
+
+    pub fn build(self) -> Source {
+        Source {
+            tokens: self.tokens,
+        }
+    }
+}
+
+impl Source {
+    /// Formats the record as SOURCE lines, without line endings. Each token starts
+    /// a new line, values too long for a line are wrapped at spaces onto continuation lines.
+    pub fn to_pdb_lines(&self) -> Vec<String> {
+        continued_record_lines("SOURCE", token_texts(&self.tokens))
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use super::*;
+
+    #[test]
+    fn builder() {
+        let source = SourceBuilder::new()
+            .mol_id(1)
+            .organism_scientific("HOMO SAPIENS")
+            .organism_taxid(9606)
+            .expression_system("ESCHERICHIA COLI")
+            .expression_system_taxid(562)
+            .build();
+        let lines = source.to_pdb_lines();
+        assert_eq!(lines[0], "SOURCE    MOL_ID: 1;");
+        assert_eq!(lines[4], "SOURCE   5 EXPRESSION_SYSTEM_TAXID: 562;");
+        let text = lines.join("\n") + "\n";
+        match source_token_parser(text.as_bytes()) {
+            Ok((_, Record::Source(parsed))) => assert_eq!(parsed.tokens, source.tokens),
+            e => panic!("{:?}", e),
+        }
+    }
 
     #[test]
     fn source() {
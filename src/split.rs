@@ -7,11 +7,11 @@ It will identify the PDB entries that are required to reconstitute a complete co
 use super::{ast::types::*, primitive::*};
 use nom::{
     character::complete::{line_ending, space0, space1},
-    do_parse, fold_many1, map, named, opt, take,
+    do_parse, map, named, opt, take,
 };
 
 use crate::make_line_folder;
-use std::{marker::PhantomData, str, str::FromStr};
+use std::marker::PhantomData;
 
 #[allow(dead_code)]
 struct SplitLine;
@@ -20,14 +20,15 @@ named!(
     split_line_parser<Continuation<SplitLine>>,
     do_parse!(
         split
-            >> take!(2)
+            >> take!(3)
             >> cont: opt!(twodigit_integer)
             >> space1
             >> rest: till_line_ending
             >> line_ending
             >> (Continuation::<SplitLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
-                remaining: String::from_str(str::from_utf8(rest).unwrap()).unwrap(),
+                continuation: cont.unwrap_or_default(),
+                remaining: String::from_utf8_lossy(rest).into_owned(),
+                line_number: None,
                 phantom: PhantomData,
             })
     )
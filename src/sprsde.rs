@@ -5,12 +5,12 @@ The SPRSDE records contain a list of the ID codes of entries that were made obso
 use super::{ast::types::*, primitive::*};
 use nom::{
     character::complete::{line_ending, space0, space1},
-    do_parse, fold_many1, map, named, opt,
+    do_parse, map, named, opt, take,
 };
 
 use crate::make_line_folder;
 
-use std::{marker::PhantomData, str, str::FromStr};
+use std::marker::PhantomData;
 
 #[allow(dead_code)]
 struct SprsdeLine;
@@ -19,14 +19,15 @@ named!(
     sprsde_line_parser<Continuation<SprsdeLine>>,
     do_parse!(
         sprsde
+            >> take!(2)
+            >> cont: opt!(twodigit_integer)
             >> space1
-            >> cont: opt!(integer)
-            >> space0
             >> rest: till_line_ending
             >> line_ending
             >> (Continuation::<SprsdeLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
-                remaining: String::from_str(str::from_utf8(rest).unwrap()).unwrap(),
+                continuation: cont.unwrap_or_default(),
+                remaining: String::from_utf8_lossy(rest).into_owned(),
+                line_number: None,
                 phantom: PhantomData,
             })
     )
@@ -40,9 +41,9 @@ named!(
         space0
             >> sprsde_date: date_parser
             >> space1
-            >> id_code: alphanum_word
+            >> id_code: idcode_parser
             >> space1
-            >> superseeded: idcode_list
+            >> superseeded: pdb_idcode_list
             >> (Record::Sprsde(Sprsde {
                 sprsde_date,
                 id_code,
@@ -82,8 +83,8 @@ Record layout :
             res
         } else {
             Record::Sprsde(Sprsde {
-                sprsde_date: chrono::naive::MIN_DATE,
-                id_code: String::new(),
+                sprsde_date: chrono::NaiveDate::MIN,
+                id_code: IdCode::default(),
                 superseeded: Vec::new(),
             })
         }
@@ -0,0 +1,156 @@
+/*!
+Contains parsers related to [SSBOND](http://www.wwpdb.org/documentation/file-format-content/format33/sect6.html#SSBOND)
+records. SSBOND records identify each disulfide bond in protein and polypeptide structures.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{
+    character::complete::{anychar, line_ending},
+    do_parse, named, take, take_str,
+};
+
+named!(
+    #[doc=r#"Parses a single SSBOND record. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing a [SsBond](../ast/types/struct.SsBond.html) instance. Trailing symmetry operator
+and length columns may be left out.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE    | FIELD       | DEFINITION                                |
+|----------|---------------|-------------|-------------------------------------------|
+| 1 -  6   | Record name   | SSBOND      |                                           |
+| 8 - 10   | Integer       | serNum      | Serial number.                            |
+| 12 - 14  | LString(3)    | "CYS"       | Residue name.                             |
+| 16       | Character     | chainID1    | Chain identifier.                         |
+| 18 - 21  | Integer       | seqNum1     | Residue sequence number.                  |
+| 22       | AChar         | icode1      | Insertion code.                           |
+| 26 - 28  | LString(3)    | "CYS"       | Residue name.                             |
+| 30       | Character     | chainID2    | Chain identifier.                         |
+| 32 - 35  | Integer       | seqNum2     | Residue sequence number.                  |
+| 36       | AChar         | icode2      | Insertion code.                           |
+| 60 - 65  | SymOP         | sym1        | Symmetry operator for residue 1.          |
+| 67 - 72  | SymOP         | sym2        | Symmetry operator for residue 2.          |
+| 74 – 78  | Real(5.2)     | Length      | Disulfide bond distance                   |
+"#],
+    pub ssbond_record_parser<Record>,
+    do_parse!(
+        ssbond
+            >> take!(1)
+            >> serial: threedigit_integer
+            >> take!(1)
+            >> residue_name_1: take_str!(3)
+            >> take!(1)
+            >> chain_id_1: anychar
+            >> take!(1)
+            >> residue_seq_1: residue_seq_parser
+            >> insertion_code_1: optional_char
+            >> take!(3)
+            >> residue_name_2: take_str!(3)
+            >> take!(1)
+            >> chain_id_2: anychar
+            >> take!(1)
+            >> residue_seq_2: residue_seq_parser
+            >> insertion_code_2: optional_char
+            >> rest: till_line_ending
+            >> line_ending
+            >> (Record::SsBond(SsBond {
+                serial,
+                residue_name_1: residue_name_1.trim().to_owned(),
+                chain_id_1,
+                residue_seq_1,
+                insertion_code_1,
+                residue_name_2: residue_name_2.trim().to_owned(),
+                chain_id_2,
+                residue_seq_2,
+                insertion_code_2,
+                symmetry_1: Some(text_field(rest, 23, 29))
+                    .filter(|s| !s.is_empty())
+                    .map(String::from),
+                symmetry_2: Some(text_field(rest, 30, 36))
+                    .filter(|s| !s.is_empty())
+                    .map(String::from),
+                length: text_field(rest, 37, 42).parse().ok(),
+            }))
+    )
+);
+
+/// Finds the SG atom of the cysteine `residue` among `atoms`
+pub fn sulfur_atom<'a>(
+    atoms: impl IntoIterator<Item = &'a Atom>,
+    residue: &ResidueId,
+) -> Option<&'a Atom> {
+    atoms
+        .into_iter()
+        .find(|a| a.name == "SG" && a.residue_id() == *residue)
+}
+
+/// Distance in Angstroms between the SG atoms of the two cysteines bonded by `ssbond`,
+/// `None` if either of them is missing from `atoms`
+pub fn ssbond_distance(ssbond: &SsBond, atoms: &[Atom]) -> Option<f64> {
+    let first = sulfur_atom(
+        atoms,
+        &ResidueId {
+            chain_id: ssbond.chain_id_1,
+            residue_seq: ssbond.residue_seq_1,
+            insertion_code: ssbond.insertion_code_1,
+        },
+    )?;
+    let second = sulfur_atom(
+        atoms,
+        &ResidueId {
+            chain_id: ssbond.chain_id_2,
+            residue_seq: ssbond.residue_seq_2,
+            insertion_code: ssbond.insertion_code_2,
+        },
+    )?;
+    Some(first.distance(second))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ssbond() {
+        match ssbond_record_parser(
+            "SSBOND   1 CYS A    6    CYS A  127                          1555   1555  2.03  \n"
+                .as_bytes(),
+        ) {
+            Ok((_, Record::SsBond(res))) => {
+                assert_eq!(res.serial, 1);
+                assert_eq!(res.residue_seq_1, 6);
+                assert_eq!(res.chain_id_2, 'A');
+                assert_eq!(res.residue_seq_2, 127);
+                assert_eq!(res.symmetry_1, Some("1555".to_owned()));
+                assert_eq!(res.length, Some(2.03));
+            }
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn distance() {
+        let (_, pdb) = crate::parse(
+            r#"SSBOND   1 CYS A    6    CYS A  127                          1555   1555  2.03  
+ATOM     48  SG  CYS A   6      10.449  -2.264  12.612  1.00 14.25           S
+ATOM    980  SG  CYS A 127       9.602  -0.625  11.812  1.00 14.84           S
+"#,
+        )
+        .unwrap();
+        let atoms = pdb
+            .records()
+            .iter()
+            .filter_map(|r| match r {
+                Record::Atom(a) => Some(a.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        match &pdb.records()[0] {
+            Record::SsBond(s) => {
+                let distance = ssbond_distance(s, &atoms).unwrap();
+                assert!((distance - 2.03).abs() < 0.05, "{}", distance);
+                assert_eq!(ssbond_distance(s, &atoms[..1]), None);
+            }
+            r => panic!("{:?}", r),
+        }
+    }
+}
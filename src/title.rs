@@ -5,7 +5,7 @@ records.The TITLE record contains a title for the experiment or analysis that is
 use super::{ast::types::*, primitive::*};
 use nom::{
     character::complete::{line_ending, space0, space1},
-    do_parse, fold_many1, map, named, opt, take,
+    do_parse, map, named, opt, take,
 };
 
 use crate::make_line_folder;
@@ -25,8 +25,9 @@ named!(
             >> space0
             >> line_ending
             >> (Continuation::<TitleLine> {
-                continuation: if let Some(cc) = cont { cc } else { 0 },
+                continuation: cont.unwrap_or_default(),
                 remaining: tit,
+                line_number: None,
                 phantom: PhantomData,
             })
     )
@@ -59,3 +60,21 @@ Record structure is given below
         }
     )
 );
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn three_lines() {
+        let (_, pdb) = crate::parse(
+            r#"TITLE     CRYSTAL STRUCTURE OF THE Escherichia coli                            
+TITLE    2  ALKALINE PHOSPHATASE                                               
+TITLE    3 IN COMPLEX WITH PHOSPHATE   
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            pdb.title(),
+            Some("CRYSTAL STRUCTURE OF THE Escherichia coli ALKALINE PHOSPHATASE IN COMPLEX WITH PHOSPHATE")
+        );
+    }
+}
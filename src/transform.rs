@@ -0,0 +1,238 @@
+/*!
+Contains parsers related to [CRYST1](http://www.wwpdb.org/documentation/file-format-content/format33/sect8.html#CRYST1),
+[ORIGXn](http://www.wwpdb.org/documentation/file-format-content/format33/sect8.html#ORIGXn),
+[SCALEn](http://www.wwpdb.org/documentation/file-format-content/format33/sect8.html#SCALEn) and
+[MTRIXn](http://www.wwpdb.org/documentation/file-format-content/format33/sect8.html#MTRIXn) records of the
+coordinate transformation section. CRYST1 holds the unit cell of the crystal, the others are given as
+three numbered lines holding a row of a transformation matrix and an element of a translation vector.
+*/
+use super::{ast::types::*, primitive::*};
+use nom::{
+    call,
+    character::complete::{anychar, line_ending},
+    do_parse, map, named, peek, tag, take,
+};
+
+named!(
+    #[doc=r#"Parses a CRYST1 record. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing [Cryst1](../ast/types/struct.Cryst1.html) instance. A missing Z value is read as 0.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE    | FIELD       | DEFINITION               |
+|----------|---------------|-------------|--------------------------|
+| 1 -  6   | Record name   | CRYST1      |                          |
+| 7 - 15   | Real(9.3)     | a           | a (Angstroms).           |
+| 16 - 24  | Real(9.3)     | b           | b (Angstroms).           |
+| 25 - 33  | Real(9.3)     | c           | c (Angstroms).           |
+| 34 - 40  | Real(7.2)     | alpha       | alpha (degrees).         |
+| 41 - 47  | Real(7.2)     | beta        | beta (degrees).          |
+| 48 - 54  | Real(7.2)     | gamma       | gamma (degrees).         |
+| 56 - 66  | LString       | sGroup      | Space  group.            |
+| 67 - 70  | Integer       | z           | Z value.                 |
+"#],
+    pub cryst1_record_parser<Record>,
+    do_parse!(
+        cryst1
+            >> a: call!(real(9))
+            >> b: call!(real(9))
+            >> c: call!(real(9))
+            >> alpha: call!(real(7))
+            >> beta: call!(real(7))
+            >> gamma: call!(real(7))
+            >> rest: till_line_ending
+            >> line_ending
+            >> (Record::Cryst1(Cryst1 {
+                a,
+                b,
+                c,
+                alpha,
+                beta,
+                gamma,
+                space_group: text_field(rest, 1, 12).to_owned(),
+                z: text_field(rest, 12, 16).parse().unwrap_or(0),
+            }))
+    )
+);
+
+impl Cryst1 {
+    /// Z value, the number of polymeric chains in a unit cell
+    pub fn molecules_per_cell(&self) -> u32 {
+        self.z
+    }
+}
+
+named!(
+    #[doc=r#"Parses ORIGX1, ORIGX2 and ORIGX3 lines. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing [Origx](../ast/types/struct.Origx.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD    | DEFINITION  |
+|----------|--------------|----------|-------------|
+| 1 -  6   | Record name  | ORIGXn   | n=1, 2, or 3|
+| 11 - 20  | Real(10.6)   | o[n][1]  | On1         |
+| 21 - 30  | Real(10.6)   | o[n][2]  | On2         |
+| 31 - 40  | Real(10.6)   | o[n][3]  | On3         |
+| 46 - 55  | Real(10.5)   | t[n]     | Tn          |
+"#],
+    pub origx_record_parser<Record>,
+    map!(call!(three_line_matrix_parser("ORIGX")), |(matrix, translation)| {
+        Record::Origx(Origx {
+            matrix,
+            translation,
+        })
+    })
+);
+
+named!(
+    #[doc=r#"Parses SCALE1, SCALE2 and SCALE3 lines. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing [Scale](../ast/types/struct.Scale.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD    | DEFINITION  |
+|----------|--------------|----------|-------------|
+| 1 -  6   | Record name  | SCALEn   | n=1, 2, or 3|
+| 11 - 20  | Real(10.6)   | s[n][1]  | Sn1         |
+| 21 - 30  | Real(10.6)   | s[n][2]  | Sn2         |
+| 31 - 40  | Real(10.6)   | s[n][3]  | Sn3         |
+| 46 - 55  | Real(10.5)   | u[n]     | Un          |
+"#],
+    pub scale_record_parser<Record>,
+    map!(call!(three_line_matrix_parser("SCALE")), |(matrix, translation)| {
+        Record::Scale(Scale {
+            matrix,
+            translation,
+        })
+    })
+);
+
+named!(
+    mtrix_info_parser<(u32, bool)>,
+    do_parse!(
+        mtrix
+            >> tag!("1 ")
+            >> serial: threedigit_integer
+            >> take!(49)
+            >> given: anychar
+            >> ((serial, given == '1'))
+    )
+);
+
+named!(
+    #[doc=r#"Parses MTRIX1, MTRIX2 and MTRIX3 lines. If successfull returns [Record](../ast/types/enum.Record.html)
+variant containing [Mtrix](../ast/types/struct.Mtrix.html) instance.
+
+Record structure :
+
+| COLUMNS  | DATA  TYPE   | FIELD    | DEFINITION                                  |
+|----------|--------------|----------|---------------------------------------------|
+| 1 -  6   | Record name  | MTRIXn   | n=1, 2, or 3                                |
+| 8 - 10   | Integer      | serial   | Serial number.                              |
+| 11 - 20  | Real(10.6)   | m[n][1]  | Mn1                                         |
+| 21 - 30  | Real(10.6)   | m[n][2]  | Mn2                                         |
+| 31 - 40  | Real(10.6)   | m[n][3]  | Mn3                                         |
+| 46 - 55  | Real(10.5)   | v[n]     | Vn                                          |
+| 60       | Integer      | iGiven   | 1 if coordinates for the representations    |
+|          |              |          | which are approximately related by the      |
+|          |              |          | transformations of the molecule are         |
+|          |              |          | contained in the entry. Otherwise, blank.   |
+"#],
+    pub mtrix_record_parser<Record>,
+    do_parse!(
+        info: peek!(mtrix_info_parser)
+            >> transformation: call!(three_line_matrix_parser("MTRIX"))
+            >> (Record::Mtrix(Mtrix {
+                serial: info.0,
+                matrix: transformation.0,
+                translation: transformation.1,
+                given: info.1,
+            }))
+    )
+);
+
+/// Operators of `mtrices` whose coordinates are not present in the file and have to be
+/// generated by applying them, in the order given
+pub fn mtrix_to_generate(mtrices: &[Mtrix]) -> Vec<&Mtrix> {
+    mtrices.iter().filter(|m| !m.given).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cryst1() {
+        match cryst1_record_parser(
+            "CRYST1   52.000   58.600   61.900  90.00  90.00  90.00 P 21 21 21    8          \n"
+                .as_bytes(),
+        ) {
+            Ok((_, Record::Cryst1(res))) => {
+                assert_eq!(res.a, 52.0);
+                assert_eq!(res.gamma, 90.0);
+                assert_eq!(res.space_group, "P 21 21 21");
+                assert_eq!(res.molecules_per_cell(), 8);
+            }
+            e => panic!("{:?}", e),
+        }
+    }
+
+    #[test]
+    fn scale() {
+        let input =
+            r#"SCALE1      0.013399  0.000000  0.000000        0.00000                         
+SCALE2      0.000000  0.020049  0.000000        0.00000                         
+SCALE3      0.000000  0.000000  0.012124        0.00000                         
+"#
+            .as_bytes();
+        let (_, (matrix, vector)) = three_line_matrix_parser("SCALE")(input).unwrap();
+        assert_eq!(matrix[0][0], 0.013399);
+        assert_eq!(matrix[1][1], 0.020049);
+        assert_eq!(matrix[2][2], 0.012124);
+        assert_eq!(vector, [0.0, 0.0, 0.0]);
+
+        if let Ok((_, Record::Scale(scale))) = scale_record_parser(input) {
+            assert_eq!(scale.matrix, matrix);
+        } else {
+            panic!();
+        }
+    }
+
+    #[test]
+    fn mtrix() {
+        let res = mtrix_record_parser(
+            r#"MTRIX1   1 -1.000000  0.000000 -0.000000        0.00001    1                    
+MTRIX2   1 -0.000000  1.000000  0.000000        0.00002    1                    
+MTRIX3   1  0.000000 -0.000000 -1.000000        0.00002    1                    
+"#
+            .as_bytes(),
+        );
+        if let Ok((_, Record::Mtrix(mtrix))) = res {
+            assert_eq!(mtrix.serial, 1);
+            assert!(mtrix.given);
+            assert_eq!(mtrix.matrix[0][0], -1.0);
+            assert_eq!(mtrix.translation[1], 0.00002);
+        } else {
+            panic!();
+        }
+    }
+
+    #[test]
+    fn to_generate() {
+        let mtrices = (1..=3)
+            .map(|serial| Mtrix {
+                serial,
+                given: serial == 1,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            mtrix_to_generate(&mtrices)
+                .iter()
+                .map(|m| m.serial)
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+    }
+}
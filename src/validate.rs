@@ -0,0 +1,459 @@
+/*!
+Contains [validate](fn.validate.html), a single quality check entry point running all available checks
+over a parsed entry and collecting their findings into a [ValidationReport](../ast/types/struct.ValidationReport.html).
+*/
+use super::{
+    ast::pdb_file::PdbFile,
+    ast::types::*,
+    expdta::{check_method_cell, check_method_resolution},
+    master::check_master_counts,
+    revdat::check_revdat_idcodes,
+    seqres::seqres_by_chain,
+};
+use std::collections::{HashMap, HashSet};
+
+/// section rank and record name used while checking record order
+pub(crate) fn record_section(record: &Record) -> Option<(u8, &'static str)> {
+    Some(match record {
+        Record::Header(_) => (0, "HEADER"),
+        Record::Obslte(_) => (1, "OBSLTE"),
+        Record::Title(_) => (2, "TITLE"),
+        Record::Split(_) => (3, "SPLIT"),
+        Record::Caveat(_) => (4, "CAVEAT"),
+        Record::Cmpnd(_) => (5, "COMPND"),
+        Record::Source(_) => (6, "SOURCE"),
+        Record::Keywds(_) => (7, "KEYWDS"),
+        Record::Experimental(_) => (8, "EXPDTA"),
+        Record::Nummdl(_) => (9, "NUMMDL"),
+        Record::Mdltyp(_) => (10, "MDLTYP"),
+        Record::Authors(_) => (11, "AUTHOR"),
+        Record::Revdats(_) => (12, "REVDAT"),
+        Record::Sprsde(_) => (13, "SPRSDE"),
+        Record::JournalAuthors(_)
+        | Record::JournalTitle(_)
+        | Record::JournalEditors(_)
+        | Record::JournalReference(_)
+        | Record::JournalCitation(_)
+        | Record::JournalPublication(_)
+        | Record::JournalPubMedId(_)
+        | Record::JournalDoi(_) => (14, "JRNL"),
+        Record::Resolution(_)
+        | Record::FormatVersion(_)
+        | Record::RefinementStats(_)
+        | Record::SiteDescriptions(_)
+        | Record::BiologicalAssemblies(_)
+        | Record::MissingResidues(_)
+        | Record::Remark(_) => (15, "REMARK"),
+        Record::Dbref(_) | Record::Dbref1(_) | Record::Dbref2(_) => (16, "DBREF"),
+        Record::Seqadv(_) => (17, "SEQADV"),
+        Record::Seqres(_) => (18, "SEQRES"),
+        Record::Het(_) => (19, "HET"),
+        Record::HetName(_) => (20, "HETNAM"),
+        Record::Formul(_) => (21, "FORMUL"),
+        Record::Helix(_) => (22, "HELIX"),
+        Record::Sheet(_) => (23, "SHEET"),
+        Record::SsBond(_) => (24, "SSBOND"),
+        Record::CisPep(_) => (25, "CISPEP"),
+        Record::Site(_) => (26, "SITE"),
+        Record::Cryst1(_) => (27, "CRYST1"),
+        Record::Origx(_) => (28, "ORIGX"),
+        Record::Scale(_) => (29, "SCALE"),
+        Record::Mtrix(_) => (30, "MTRIX"),
+        Record::Model(_) | Record::Atom(_) | Record::Ter(_) => (31, "ATOM"),
+        Record::Conect(_) => (32, "CONECT"),
+        Record::Master(_) => (33, "MASTER"),
+        Record::FootNote(_) => return None,
+    })
+}
+
+/// true for records of the title section, HEADER through JRNL
+pub(crate) fn in_title_section(record: &Record) -> bool {
+    matches!(record_section(record), Some((rank, _)) if rank <= 14)
+}
+
+fn check_order(records: &[Record]) -> Vec<ParseWarning> {
+    let mut latest: Option<(u8, &'static str)> = None;
+    let mut seen = HashSet::new();
+    let mut warnings = Vec::new();
+    for (rank, name) in records.iter().filter_map(record_section) {
+        match latest {
+            Some((latest_rank, latest_name)) if rank < latest_rank => {
+                if seen.insert((name, latest_name)) {
+                    warnings.push(ParseWarning::RecordOutOfOrder {
+                        record: name,
+                        after: latest_name,
+                    });
+                }
+            }
+            _ => latest = Some((rank, name)),
+        }
+    }
+    warnings
+}
+
+fn check_seqres_atoms(pdb: &PdbFile<Vec<Record>>) -> Vec<ParseWarning> {
+    let seqres = pdb
+        .records()
+        .iter()
+        .filter_map(|r| match r {
+            Record::Seqres(s) => Some((s.chain_id.unwrap_or(' '), s.residues.len())),
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+    if seqres.is_empty() {
+        return Vec::new();
+    }
+    let mut residues: Vec<(char, HashSet<ResidueId>)> = Vec::new();
+    for a in pdb.first_model_atoms().filter(|a| !a.hetero) {
+        match residues.iter_mut().find(|(c, _)| *c == a.chain_id) {
+            Some((_, ids)) => {
+                ids.insert(a.residue_id());
+            }
+            None => residues.push((a.chain_id, std::iter::once(a.residue_id()).collect())),
+        }
+    }
+    residues
+        .into_iter()
+        .filter_map(|(chain, ids)| match seqres.get(&chain) {
+            None => Some(ParseWarning::ChainWithoutSeqres(chain)),
+            Some(len) if ids.len() > *len => Some(ParseWarning::CountMismatch {
+                record: "ATOM",
+                expected: *len,
+                found: ids.len(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Checks that residues located in the first model together with the residues listed as
+/// missing in REMARK 465 cover the SEQRES sequence of each chain. Residues of the first
+/// model are counted when their name is part of the sequence, which takes modified residues
+/// given as HETATM into account. Entries without REMARK 465 are not checked.
+fn check_missing_residues(pdb: &PdbFile<Vec<Record>>) -> Vec<ParseWarning> {
+    let missing = pdb
+        .records()
+        .iter()
+        .filter_map(|r| match r {
+            Record::MissingResidues(m) => Some(m),
+            _ => None,
+        })
+        .flatten()
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return Vec::new();
+    }
+    let serial = pdb.model_serials().next().unwrap_or(1);
+    seqres_by_chain(pdb.records())
+        .into_iter()
+        .filter_map(|(chain, sequence)| {
+            let mut residues = pdb
+                .first_model_atoms()
+                .filter(|a| a.chain_id == chain && sequence.contains(&a.residue_name))
+                .map(|a| a.residue_id())
+                .collect::<HashSet<_>>();
+            residues.extend(
+                missing
+                    .iter()
+                    .filter(|m| m.residue_id.chain_id == chain)
+                    .filter(|m| m.model.is_none_or(|model| model == serial))
+                    .map(|m| m.residue_id),
+            );
+            Some(ParseWarning::CountMismatch {
+                record: "REMARK 465",
+                expected: sequence.len(),
+                found: residues.len(),
+            })
+            .filter(|_| residues.len() != sequence.len())
+        })
+        .collect()
+}
+
+/// chain identifiers listed after `CHAIN` in a MDLTYP annotation such as
+/// `CA ATOMS ONLY, CHAIN A, B`
+fn mdltyp_chains(annotation: &str) -> Vec<char> {
+    annotation
+        .split_once("CHAIN")
+        .map(|(_, chains)| {
+            chains
+                .split(',')
+                .filter_map(|c| {
+                    let mut chars = c.trim().chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => Some(c),
+                        _ => None,
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Checks NUMMDL against the MODEL records and the chains named in MDLTYP against the
+/// chains of the first model
+fn check_ensemble(pdb: &PdbFile<Vec<Record>>) -> Vec<(IssueCategory, ParseWarning)> {
+    let models = pdb.model_serials().count();
+    let mut issues = Vec::new();
+    if let Some(nummdl) = pdb.records().iter().find_map(|r| match r {
+        Record::Nummdl(n) => Some(n),
+        _ => None,
+    }) {
+        if nummdl.num as usize != models {
+            issues.push((
+                IssueCategory::Counts,
+                ParseWarning::CountMismatch {
+                    record: "NUMMDL",
+                    expected: nummdl.num as usize,
+                    found: models,
+                },
+            ));
+        }
+    }
+    let chains = pdb
+        .first_model_atoms()
+        .map(|a| a.chain_id)
+        .collect::<HashSet<_>>();
+    let mut seen = HashSet::new();
+    let unknown = pdb
+        .records()
+        .iter()
+        .filter_map(|r| match r {
+            Record::Mdltyp(m) => Some(m),
+            _ => None,
+        })
+        .flat_map(|m| m.structural_annotation.iter())
+        .flat_map(|a| mdltyp_chains(a))
+        .filter(|c| !chains.contains(c) && seen.insert(*c))
+        .collect::<Vec<_>>();
+    issues.extend(unknown.into_iter().map(|chain_id| {
+        (
+            IssueCategory::Consistency,
+            ParseWarning::UnknownChain {
+                record: "MDLTYP",
+                chain_id,
+            },
+        )
+    }));
+    issues
+}
+
+/// Runs all available checks over `pdb` and collects their findings by category:
+///
+/// - counts of SEQRES records against the residues listed
+/// - polymer chains of the first model against their SEQRES records
+/// - HELIX lengths against their residue ranges
+/// - for NMR entries, NUMMDL against the MODEL records and chains named in MDLTYP against
+///   the coordinates
+/// - line counts given in MASTER against the records of the entry
+/// - SEQRES sequences against the located residues and the missing residues of REMARK 465
+/// - REVDAT id codes against the HEADER id code
+/// - order of records against the sections of the format
+/// - experimental method against the REMARK 2 resolution
+/// - experimental method against the CRYST1 Z value
+/// - plausibility of taxonomy ids
+pub fn validate(pdb: &PdbFile<Vec<Record>>) -> ValidationReport {
+    let mut issues = pdb
+        .warnings()
+        .into_iter()
+        .map(|w| match w {
+            ParseWarning::CountMismatch { .. } | ParseWarning::ModelGap { .. } => {
+                (IssueCategory::Counts, w)
+            }
+            ParseWarning::SeqresSerialOutOfOrder { .. } => (IssueCategory::Ordering, w),
+            ParseWarning::HelixLengthMismatch { .. } => (IssueCategory::Consistency, w),
+            _ => (IssueCategory::Plausibility, w),
+        })
+        .collect::<Vec<_>>();
+    issues.extend(
+        check_seqres_atoms(pdb)
+            .into_iter()
+            .map(|w| (IssueCategory::Consistency, w)),
+    );
+    let experimental = pdb.records().iter().find_map(|r| match r {
+        Record::Experimental(e) => Some(e),
+        _ => None,
+    });
+    if experimental.is_some_and(Experimental::includes_nmr) {
+        issues.extend(check_ensemble(pdb));
+    }
+    for master in pdb.records().iter().filter_map(|r| match r {
+        Record::Master(m) => Some(m),
+        _ => None,
+    }) {
+        issues.extend(
+            check_master_counts(master, pdb.records())
+                .into_iter()
+                .map(|w| (IssueCategory::Counts, w)),
+        );
+    }
+    issues.extend(
+        check_missing_residues(pdb)
+            .into_iter()
+            .map(|w| (IssueCategory::Consistency, w)),
+    );
+    issues.extend(
+        check_order(pdb.records())
+            .into_iter()
+            .map(|w| (IssueCategory::Ordering, w)),
+    );
+    let header = pdb.records().iter().find_map(|r| match r {
+        Record::Header(h) => Some(h),
+        _ => None,
+    });
+    for revdats in pdb.records().iter().filter_map(|r| match r {
+        Record::Revdats(r) => Some(r),
+        _ => None,
+    }) {
+        issues.extend(
+            header
+                .map(|h| check_revdat_idcodes(h, revdats))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|w| (IssueCategory::Consistency, w)),
+        );
+    }
+    let resolution = pdb.records().iter().find_map(|r| match r {
+        Record::Resolution(r) => r.angstroms,
+        _ => None,
+    });
+    if let Some(w) = experimental.and_then(|e| check_method_resolution(e, resolution)) {
+        issues.push((IssueCategory::Method, w));
+    }
+    let cryst1 = pdb.records().iter().find_map(|r| match r {
+        Record::Cryst1(c) => Some(c),
+        _ => None,
+    });
+    if let Some(w) = experimental
+        .zip(cryst1)
+        .and_then(|(e, c)| check_method_cell(e, c))
+    {
+        issues.push((IssueCategory::Method, w));
+    }
+    ValidationReport { issues }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn inconsistent() {
+        let (_, pdb) = crate::parse(
+            r#"EXPDTA    SOLUTION NMR
+REMARK   2
+REMARK   2 RESOLUTION.    2.10 ANGSTROMS.
+SEQRES   1 A    3  MET ALA
+ATOM      1  N   MET A   1      47.699  22.521  30.384  1.00 37.82           N
+ATOM      2  N   GLY B   1      48.661  22.866  29.347  1.00 27.31           N
+KEYWDS    HYDROLASE
+"#,
+        )
+        .unwrap();
+        let report = validate(&pdb);
+        assert_eq!(
+            report.category(IssueCategory::Counts).collect::<Vec<_>>(),
+            vec![&ParseWarning::CountMismatch {
+                record: "SEQRES",
+                expected: 3,
+                found: 2
+            }]
+        );
+        assert_eq!(
+            report
+                .category(IssueCategory::Consistency)
+                .collect::<Vec<_>>(),
+            vec![&ParseWarning::ChainWithoutSeqres('B')]
+        );
+        assert_eq!(
+            report.category(IssueCategory::Ordering).collect::<Vec<_>>(),
+            vec![&ParseWarning::RecordOutOfOrder {
+                record: "KEYWDS",
+                after: "ATOM"
+            }]
+        );
+        assert_eq!(
+            report.category(IssueCategory::Method).collect::<Vec<_>>(),
+            vec![&ParseWarning::NmrWithResolution(2.1)]
+        );
+    }
+
+    #[test]
+    fn consistent() {
+        let (_, pdb) = crate::parse(
+            r#"EXPDTA    X-RAY DIFFRACTION
+SEQRES   1 A    2  MET ALA
+ATOM      1  N   MET A   1      47.699  22.521  30.384  1.00 37.82           N
+"#,
+        )
+        .unwrap();
+        assert!(validate(&pdb).is_empty());
+    }
+
+    #[test]
+    fn ensemble() {
+        let input = r#"EXPDTA    SOLUTION NMR
+NUMMDL    2
+MDLTYP    CA ATOMS ONLY, CHAIN A, B
+MODEL        1
+ATOM      1  CA  MET A   1      47.699  22.521  30.384  1.00 37.82           C
+ENDMDL
+"#;
+        let (_, pdb) = crate::parse(input).unwrap();
+        let report = validate(&pdb);
+        assert_eq!(
+            report.category(IssueCategory::Counts).collect::<Vec<_>>(),
+            vec![&ParseWarning::CountMismatch {
+                record: "NUMMDL",
+                expected: 2,
+                found: 1
+            }]
+        );
+        assert_eq!(
+            report
+                .category(IssueCategory::Consistency)
+                .collect::<Vec<_>>(),
+            vec![&ParseWarning::UnknownChain {
+                record: "MDLTYP",
+                chain_id: 'B'
+            }]
+        );
+        let (_, pdb) = crate::parse(&input.replace("SOLUTION NMR", "X-RAY DIFFRACTION")).unwrap();
+        let report = validate(&pdb);
+        assert_eq!(report.category(IssueCategory::Counts).count(), 0);
+        assert_eq!(report.category(IssueCategory::Consistency).count(), 0);
+    }
+
+    #[test]
+    fn master_and_missing_residues() {
+        let (_, pdb) = crate::parse(
+            r#"EXPDTA    X-RAY DIFFRACTION
+REMARK 465   M RES C SSSEQI
+REMARK 465     MET A     1
+SEQRES   1 A    4  MET ALA GLY SER
+ATOM      1  N   ALA A   2      47.699  22.521  30.384  1.00 37.82           N
+ATOM      2  N   GLY A   3      48.661  22.866  29.347  1.00 27.31           N
+MASTER        0    0    0    0    0    0    0    0    3    0    0    1
+"#,
+        )
+        .unwrap();
+        let report = validate(&pdb);
+        assert_eq!(
+            report.category(IssueCategory::Counts).collect::<Vec<_>>(),
+            vec![&ParseWarning::MasterCountMismatch {
+                record: "ATOM/HETATM",
+                expected: 3,
+                found: 2
+            }]
+        );
+        assert_eq!(
+            report
+                .category(IssueCategory::Consistency)
+                .collect::<Vec<_>>(),
+            vec![&ParseWarning::CountMismatch {
+                record: "REMARK 465",
+                expected: 4,
+                found: 3
+            }]
+        );
+    }
+}
@@ -0,0 +1,886 @@
+/*!
+Contains [to_pdb_string](fn.to_pdb_string.html) writing parsed records back to the PDB format, along with
+the helpers shared by the writers of single records.
+*/
+use super::{
+    ast::{pdb_file::PdbFile, types::*},
+    validate::record_section,
+};
+use chrono::NaiveDate;
+
+/// width of the text of a continued record, columns 12-80 of continuation lines
+const CONTINUATION_WIDTH: usize = 69;
+
+/// width of the text of a JRNL sub-record, columns 20-79
+const JRNL_WIDTH: usize = 60;
+
+/// Wraps `text` at spaces into pieces of at most `width` characters. Words longer than
+/// `width` are kept whole on a piece of their own.
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split(' ') {
+        if !line.is_empty() && line.len() + 1 + word.len() > width {
+            lines.push(line);
+            line = String::new();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    lines.push(line);
+    lines
+}
+
+/// Wraps `text` at spaces into pieces fitting a line of a continued record
+pub(crate) fn wrap_words(text: &str) -> Vec<String> {
+    wrap_text(text, CONTINUATION_WIDTH)
+}
+
+/// Joins `items` with commas, as the names of AUTHOR records are, wrapping after a comma
+/// into pieces of at most `width` characters
+fn wrap_list<'a>(items: impl Iterator<Item = &'a str>, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for item in items {
+        if !line.is_empty() {
+            line.push(',');
+            if line.len() + item.len() > width {
+                lines.push(line);
+                line = String::new();
+            }
+        }
+        line.push_str(item);
+    }
+    lines.push(line);
+    lines
+}
+
+/// `KEY: VALUE;` texts of COMPND or SOURCE tokens, each starting a new line
+pub(crate) fn token_texts(tokens: &[Token]) -> Vec<String> {
+    tokens
+        .iter()
+        .flat_map(|t| wrap_words(&format!("{}: {};", t.key(), t.value_string())))
+        .collect()
+}
+
+/// Formats `texts` as the lines of a continued record such as TITLE or COMPND. The
+/// first line leaves the continuation columns blank, the following ones are numbered from 2.
+pub(crate) fn continued_record_lines(record_name: &str, texts: Vec<String>) -> Vec<String> {
+    texts
+        .into_iter()
+        .enumerate()
+        .map(|(i, text)| match i {
+            0 => format!("{:<6}    {}", record_name, text),
+            _ => format!("{:<6}{:>4} {}", record_name, i + 1, text),
+        })
+        .collect()
+}
+
+/// date formatted as `DD-MMM-YY`, such as `20-APR-99`
+fn pdb_date(date: &NaiveDate) -> String {
+    date.format("%d-%b-%y").to_string().to_uppercase()
+}
+
+/// blank for `None`, the value otherwise
+fn optional<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map_or_else(String::new, T::to_string)
+}
+
+/// Formats `texts` as the lines of the JRNL sub-record `sub_record` such as AUTH or TITL,
+/// numbering continuation lines from 2
+fn jrnl_lines(sub_record: &str, texts: Vec<String>) -> Vec<String> {
+    texts
+        .into_iter()
+        .enumerate()
+        .map(|(i, text)| match i {
+            0 => format!("JRNL        {:<4}   {}", sub_record, text),
+            _ => format!("JRNL        {:<4}{:>2} {}", sub_record, i + 1, text),
+        })
+        .collect()
+}
+
+/// REF lines of a JRNL record. Publication names longer than a line are split across
+/// continuation lines, each repeating the volume, page and year.
+fn journal_reference_lines(reference: &JournalReference) -> Vec<String> {
+    let name = reference.publication_name.chars().collect::<Vec<_>>();
+    let mut names = name
+        .chunks(27)
+        .map(|c| c.iter().collect::<String>())
+        .collect::<Vec<_>>();
+    if names.is_empty() {
+        names.push(String::new());
+    }
+    names
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| {
+            format!(
+                "JRNL        REF {:>2} {:<28}  {:<2}{:>4} {:>5} {:>4}",
+                if i == 0 {
+                    String::new()
+                } else {
+                    (i + 1).to_string()
+                },
+                name,
+                if reference.volume.is_some() { "V." } else { "" },
+                optional(&reference.volume),
+                optional(&reference.page),
+                optional(&reference.year)
+            )
+        })
+        .collect()
+}
+
+/// REVDAT lines of each modification, listing up to four modified records on a line
+fn revdat_lines(revdats: &Revdats) -> Vec<String> {
+    revdats
+        .revdat
+        .iter()
+        .flat_map(|r| {
+            let mut details = r
+                .modification_detail
+                .chunks(4)
+                .map(|d| {
+                    d.iter()
+                        .map(|d| format!("{:<6}", d))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>();
+            if details.is_empty() {
+                details.push(String::new());
+            }
+            details
+                .into_iter()
+                .enumerate()
+                .map(|(i, details)| {
+                    let line = match i {
+                        0 => format!(
+                            "REVDAT {:>3}   {} {:<4}    {}       {}",
+                            r.modification_number,
+                            pdb_date(&r.modification_date),
+                            r.idcode,
+                            match r.modification_type {
+                                ModificationType::InitialRelease => '0',
+                                ModificationType::OtherModification => '1',
+                                ModificationType::UnknownModification => '3',
+                            },
+                            details
+                        ),
+                        _ => format!(
+                            "REVDAT {:>3}{:>2}{:27}{}",
+                            r.modification_number,
+                            i + 1,
+                            "",
+                            details
+                        ),
+                    };
+                    line.trim_end().to_owned()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn dbref1_line(dbref: &Dbref1) -> String {
+    format!(
+        "{:<80}",
+        format!(
+            "DBREF1 {:<4} {} {:>4}{} {:>4}{} {:<6}               {}",
+            dbref.idcode,
+            dbref.chain_id,
+            dbref.begin.residue_seq,
+            dbref.begin.insertion_code.unwrap_or(' '),
+            dbref.end.residue_seq,
+            dbref.end.insertion_code.unwrap_or(' '),
+            dbref.database.to_string(),
+            dbref.db_idcode
+        )
+    )
+}
+
+fn dbref2_line(dbref: &Dbref2) -> String {
+    format!(
+        "{:<80}",
+        format!(
+            "DBREF2 {:<4} {}     {:<22}     {:>10}  {:>10}",
+            dbref.idcode, dbref.chain_id, dbref.db_accession, dbref.db_seq_begin, dbref.db_seq_end
+        )
+    )
+}
+
+/// DBREF line of `dbref`. References read from a DBREF1/DBREF2 pair carry no insertion
+/// codes of the database segment and are written as such a pair again.
+fn dbref_lines(dbref: &Dbref) -> Vec<String> {
+    if dbref.idbns_begin.is_none() && dbref.dbins_end.is_none() {
+        return vec![
+            dbref1_line(&Dbref1 {
+                idcode: dbref.idcode.clone(),
+                chain_id: dbref.chain_id,
+                begin: dbref.begin,
+                end: dbref.end,
+                database: dbref.database.clone(),
+                db_idcode: dbref.db_idcode.clone(),
+            }),
+            dbref2_line(&Dbref2 {
+                idcode: dbref.idcode.clone(),
+                chain_id: dbref.chain_id,
+                db_accession: dbref.db_accession.clone(),
+                db_seq_begin: dbref.db_seq_begin,
+                db_seq_end: dbref.db_seq_end,
+            }),
+        ];
+    }
+    vec![format!(
+        "{:<80}",
+        format!(
+            "DBREF  {:<4} {} {:>4}{} {:>4}{} {:<6} {:<8} {:<12} {:>5}{} {:>5}{}",
+            dbref.idcode,
+            dbref.chain_id,
+            dbref.begin.residue_seq,
+            dbref.begin.insertion_code.unwrap_or(' '),
+            dbref.end.residue_seq,
+            dbref.end.insertion_code.unwrap_or(' '),
+            dbref.database.to_string(),
+            dbref.db_accession,
+            dbref.db_idcode,
+            dbref.db_seq_begin,
+            dbref.idbns_begin.unwrap_or(' '),
+            dbref.db_seq_end,
+            dbref.dbins_end.unwrap_or(' ')
+        )
+    )]
+}
+
+fn seqadv_line(seqadv: &Seqadv) -> String {
+    format!(
+        "SEQADV {:<4} {:>3} {} {:>4}{} {:<4} {:<9} {:>3} {:>5} {}",
+        seqadv.idcode,
+        seqadv.conflicting_residue,
+        seqadv.chain_id,
+        seqadv.sequence_number,
+        seqadv.insertion_code.unwrap_or(' '),
+        seqadv.database,
+        seqadv.db_accession,
+        optional(&seqadv.sequence_db_residue),
+        optional(&seqadv.sequence_db_sequence_number),
+        seqadv.conflict
+    )
+}
+
+/// HETNAM lines of `het_name`, its name wrapped at spaces into columns 16-70
+fn hetnam_lines(het_name: &HetName) -> Vec<String> {
+    wrap_text(&het_name.name, 55)
+        .into_iter()
+        .enumerate()
+        .map(|(i, text)| {
+            format!(
+                "HETNAM  {:>2} {:>3} {}",
+                if i == 0 {
+                    String::new()
+                } else {
+                    (i + 1).to_string()
+                },
+                het_name.het_id,
+                text
+            )
+        })
+        .collect()
+}
+
+/// FORMUL lines of `formul`, its formula wrapped at spaces into columns 20-70
+fn formul_lines(formul: &Formul) -> Vec<String> {
+    wrap_text(&formul.formula, 51)
+        .into_iter()
+        .enumerate()
+        .map(|(i, text)| {
+            format!(
+                "FORMUL  {:>2}  {:>3} {:>2}{}{}",
+                formul.component_number,
+                formul.het_id,
+                if i == 0 {
+                    String::new()
+                } else {
+                    (i + 1).to_string()
+                },
+                if formul.water { '*' } else { ' ' },
+                text
+            )
+        })
+        .collect()
+}
+
+fn helix_line(helix: &Helix) -> String {
+    format!(
+        "HELIX  {:>3} {:>3} {:>3} {} {:>4}{} {:>3} {} {:>4}{}{:>2}{:<30} {:>5}",
+        helix.serial,
+        helix.helix_id,
+        helix.init_residue_name,
+        helix.init_chain_id,
+        helix.init_residue_seq,
+        helix.init_insertion_code.unwrap_or(' '),
+        helix.end_residue_name,
+        helix.end_chain_id,
+        helix.end_residue_seq,
+        helix.end_insertion_code.unwrap_or(' '),
+        optional(&helix.helix_class),
+        optional(&helix.comment),
+        optional(&helix.declared_length)
+    )
+    .trim_end()
+    .to_owned()
+}
+
+fn sheet_line(sheet: &Sheet) -> String {
+    format!(
+        "SHEET  {:>3} {:>3}{:>2} {:>3} {}{:>4}{} {:>3} {}{:>4}{}{:>2}",
+        sheet.strand,
+        sheet.sheet_id,
+        sheet.num_strands,
+        sheet.init_residue_name,
+        sheet.init_chain_id,
+        sheet.init_residue_seq,
+        sheet.init_insertion_code.unwrap_or(' '),
+        sheet.end_residue_name,
+        sheet.end_chain_id,
+        sheet.end_residue_seq,
+        sheet.end_insertion_code.unwrap_or(' '),
+        sheet.sense
+    )
+}
+
+fn ssbond_line(ssbond: &SsBond) -> String {
+    format!(
+        "SSBOND {:>3} {:>3} {} {:>4}{}   {:>3} {} {:>4}{}{:23}{:>6} {:>6} {:>5}",
+        ssbond.serial,
+        ssbond.residue_name_1,
+        ssbond.chain_id_1,
+        ssbond.residue_seq_1,
+        ssbond.insertion_code_1.unwrap_or(' '),
+        ssbond.residue_name_2,
+        ssbond.chain_id_2,
+        ssbond.residue_seq_2,
+        ssbond.insertion_code_2.unwrap_or(' '),
+        "",
+        optional(&ssbond.symmetry_1),
+        optional(&ssbond.symmetry_2),
+        ssbond
+            .length
+            .map_or_else(String::new, |l| format!("{:.2}", l))
+    )
+    .trim_end()
+    .to_owned()
+}
+
+fn cispep_line(cispep: &CisPep) -> String {
+    format!(
+        "CISPEP {:>3} {:>3} {} {:>4}{}   {:>3} {} {:>4}{}       {:>3}       {:>6.2}",
+        cispep.serial,
+        cispep.residue_name_1,
+        cispep.chain_id_1,
+        cispep.residue_seq_1,
+        cispep.insertion_code_1.unwrap_or(' '),
+        cispep.residue_name_2,
+        cispep.chain_id_2,
+        cispep.residue_seq_2,
+        cispep.insertion_code_2.unwrap_or(' '),
+        cispep.model_number,
+        cispep.measure
+    )
+}
+
+/// SITE lines of `site`, listing up to four residues on a line
+fn site_lines(site: &Site) -> Vec<String> {
+    let mut residues = site.residues.chunks(4).collect::<Vec<_>>();
+    if residues.is_empty() {
+        residues.push(&[]);
+    }
+    residues
+        .into_iter()
+        .zip(1..)
+        .map(|(residues, serial)| {
+            format!(
+                "SITE   {:>3} {:>3} {:>2}{}",
+                serial,
+                site.id,
+                site.num_residues,
+                residues
+                    .iter()
+                    .map(|(name, id)| format!(
+                        " {:>3} {}{:>4}{}",
+                        name,
+                        id.chain_id,
+                        id.residue_seq,
+                        id.insertion_code.unwrap_or(' ')
+                    ))
+                    .collect::<String>()
+            )
+            .trim_end()
+            .to_owned()
+        })
+        .collect()
+}
+
+/// The three lines of an ORIGX, SCALE or MTRIX transformation. `serial` and `given` are
+/// only written for MTRIX.
+fn transformation_lines(
+    record_name: &str,
+    matrix: &[[f64; 3]; 3],
+    translation: &[f64; 3],
+    mtrix: Option<(u32, bool)>,
+) -> Vec<String> {
+    matrix
+        .iter()
+        .zip(translation.iter())
+        .zip(1..)
+        .map(|((row, t), n)| {
+            format!(
+                "{}{}{}{:>10.6}{:>10.6}{:>10.6}     {:>10.5}{}",
+                record_name,
+                n,
+                mtrix.map_or_else(
+                    || "    ".to_owned(),
+                    |(serial, _)| format!(" {:>3}", serial)
+                ),
+                row[0],
+                row[1],
+                row[2],
+                t,
+                mtrix.map_or("", |(_, given)| if given { "    1" } else { "     " })
+            )
+        })
+        .collect()
+}
+
+fn ftnote_lines(footnote: &FootNote) -> Vec<String> {
+    wrap_text(&footnote.text, 59)
+        .into_iter()
+        .map(|text| format!("FTNOTE {:>3} {}", footnote.number, text))
+        .collect()
+}
+
+fn refinement_lines(stats: &RefinementStats) -> Vec<String> {
+    let value = |v: Option<f64>| v.map_or_else(|| "NULL".to_owned(), |v| format!("{:.3}", v));
+    vec![
+        "REMARK   3 REFINEMENT.".to_owned(),
+        format!(
+            "REMARK   3   R VALUE            (WORKING SET) : {}",
+            value(stats.r_value)
+        ),
+        format!(
+            "REMARK   3   FREE R VALUE                     : {}",
+            value(stats.r_free)
+        ),
+    ]
+}
+
+fn site_description_lines(sites: &[SiteDescription]) -> Vec<String> {
+    std::iter::once("REMARK 800".to_owned())
+        .chain(sites.iter().flat_map(|site| {
+            vec![
+                format!("SITE_IDENTIFIER: {}", site.id),
+                format!("EVIDENCE_CODE: {}", site.evidence),
+            ]
+            .into_iter()
+            .chain(wrap_words(&format!(
+                "SITE_DESCRIPTION: {}",
+                site.description
+            )))
+            .map(|text| format!("REMARK 800 {}", text).trim_end().to_owned())
+        }))
+        .collect()
+}
+
+fn biological_assembly_lines(assemblies: &[BiologicalAssembly]) -> Vec<String> {
+    std::iter::once("REMARK 350".to_owned())
+        .chain(assemblies.iter().flat_map(|assembly| {
+            let groups = assembly.groups.iter().flat_map(|group| {
+                let chains = group.chains.chunks(10).enumerate().map(|(i, chains)| {
+                    format!(
+                        "{}: {}",
+                        if i == 0 {
+                            "APPLY THE FOLLOWING TO CHAINS"
+                        } else {
+                            "                   AND CHAINS"
+                        },
+                        chains.join(", ")
+                    )
+                });
+                let operators = group.operators.iter().flat_map(|op| {
+                    (0..3).map(move |row| {
+                        format!(
+                            "  BIOMT{} {:>3}{:>10.6}{:>10.6}{:>10.6}     {:>10.5}",
+                            row + 1,
+                            op.serial,
+                            op.matrix[row][0],
+                            op.matrix[row][1],
+                            op.matrix[row][2],
+                            op.translation[row]
+                        )
+                    })
+                });
+                chains.chain(operators)
+            });
+            std::iter::once(format!("BIOMOLECULE: {}", assembly.id))
+                .chain(groups)
+                .map(|text| format!("REMARK 350 {}", text))
+                .collect::<Vec<_>>()
+        }))
+        .collect()
+}
+
+fn missing_residue_lines(residues: &[MissingResidue]) -> Vec<String> {
+    vec![
+        "REMARK 465 MISSING RESIDUES".to_owned(),
+        "REMARK 465   M RES C SSSEQI".to_owned(),
+    ]
+    .into_iter()
+    .chain(residues.iter().map(|r| {
+        format!(
+            "REMARK 465 {:>3} {:>3} {} {:>5}{}",
+            optional(&r.model),
+            r.residue_name,
+            r.residue_id.chain_id,
+            r.residue_id.residue_seq,
+            r.residue_id.insertion_code.unwrap_or(' ')
+        )
+        .trim_end()
+        .to_owned()
+    }))
+    .collect()
+}
+
+/// ANISOU line of `atom`, sharing columns 7-27 and 77-80 with its ATOM or HETATM line
+fn anisou_line(atom: &Atom, u: &[i32; 6]) -> String {
+    let line = atom.to_pdb_line();
+    format!(
+        "ANISOU{} {}      {}",
+        &line[6..27],
+        u.iter()
+            .map(|u| format!("{:>7}", u))
+            .collect::<Vec<_>>()
+            .join(""),
+        line.get(76..).unwrap_or("")
+    )
+}
+
+fn atom_lines(atom: &Atom) -> Vec<String> {
+    std::iter::once(atom.to_pdb_line())
+        .chain(atom.anisou.as_ref().map(|u| anisou_line(atom, u)))
+        .collect()
+}
+
+/// lines of `record`
+fn record_lines(record: &Record) -> Vec<String> {
+    match record {
+        Record::Header(h) => vec![format!(
+            "HEADER    {:<40}{}   {}",
+            h.classification,
+            pdb_date(&h.deposition_date),
+            h.id_code
+        )],
+        Record::Obslte(o) => continued_record_lines(
+            "OBSLTE",
+            wrap_words(&format!(
+                "{} {}",
+                pdb_date(&o.replacement_date),
+                o.replacement_ids
+                    .iter()
+                    .map(IdCode::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )),
+        ),
+        Record::Title(t) => continued_record_lines("TITLE", wrap_words(&t.title)),
+        Record::Split(s) => continued_record_lines("SPLIT", wrap_words(&s.id_codes.join(" "))),
+        Record::Caveat(c) => continued_record_lines(
+            "CAVEAT",
+            wrap_words(&format!("{}    {}", c.id_code, c.comment)),
+        ),
+        Record::Cmpnd(c) => c.to_pdb_lines(),
+        Record::Source(s) => s.to_pdb_lines(),
+        Record::Keywds(k) => continued_record_lines("KEYWDS", wrap_words(&k.keywords.join(", "))),
+        Record::Experimental(e) => continued_record_lines(
+            "EXPDTA",
+            wrap_words(
+                &e.techniques
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            ),
+        ),
+        Record::Nummdl(n) => vec![format!("NUMMDL    {}", n.num)],
+        Record::Mdltyp(m) => {
+            continued_record_lines("MDLTYP", wrap_words(&m.structural_annotation.join("; ")))
+        }
+        Record::Authors(a) => continued_record_lines(
+            "AUTHOR",
+            wrap_list(a.authors.iter().map(|a| a.0.as_str()), CONTINUATION_WIDTH),
+        ),
+        Record::Revdats(r) => revdat_lines(r),
+        Record::Sprsde(s) => continued_record_lines(
+            "SPRSDE",
+            wrap_words(&format!(
+                "{} {} {}",
+                pdb_date(&s.sprsde_date),
+                s.id_code,
+                s.superseeded
+                    .iter()
+                    .map(IdCode::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )),
+        ),
+        Record::JournalAuthors(a) => jrnl_lines(
+            "AUTH",
+            wrap_list(a.authors.iter().map(|a| a.0.as_str()), JRNL_WIDTH),
+        ),
+        Record::JournalTitle(t) => jrnl_lines("TITL", wrap_text(&t.title, JRNL_WIDTH)),
+        Record::JournalEditors(e) => jrnl_lines(
+            "EDIT",
+            wrap_list(e.name.iter().map(|a| a.0.as_str()), JRNL_WIDTH),
+        ),
+        Record::JournalReference(r) => journal_reference_lines(r),
+        Record::JournalPublication(p) => jrnl_lines("PUBL", wrap_text(&p.publication, JRNL_WIDTH)),
+        Record::JournalCitation(c) => vec![format!(
+            "JRNL        REFN                   {:<4} {}",
+            match c.serial_type {
+                Some(SerialNumber::Issn) => "ISSN",
+                Some(SerialNumber::Essn) => "ESSN",
+                None => "",
+            },
+            optional(&c.serial)
+        )],
+        Record::JournalPubMedId(p) => vec![format!("JRNL        PMID   {}", p.id)],
+        Record::JournalDoi(d) => vec![format!("JRNL        DOI    {}", d.id)],
+        Record::Resolution(r) => vec![format!(
+            "REMARK   2 RESOLUTION. {}",
+            r.angstroms.map_or_else(
+                || "NOT APPLICABLE.".to_owned(),
+                |a| format!("{:>7.2} ANGSTROMS.", a)
+            )
+        )],
+        Record::RefinementStats(r) => refinement_lines(r),
+        Record::FormatVersion(f) => vec![f.format_version.as_ref().map_or_else(
+            || "REMARK   4".to_owned(),
+            |v| format!("REMARK   4 COMPLIES WITH FORMAT V. {}", v),
+        )],
+        Record::BiologicalAssemblies(a) => biological_assembly_lines(a),
+        Record::MissingResidues(m) => missing_residue_lines(m),
+        Record::SiteDescriptions(s) => site_description_lines(s),
+        Record::Remark(text) => vec![format!("REMARK{}", text)],
+        Record::Dbref(d) => dbref_lines(d),
+        Record::Dbref1(d) => vec![dbref1_line(d)],
+        Record::Dbref2(d) => vec![dbref2_line(d)],
+        Record::Seqadv(s) => vec![seqadv_line(s)],
+        Record::Seqres(s) => s
+            .residues
+            .chunks(13)
+            .zip(1..)
+            .map(|(residues, serial)| {
+                format!(
+                    "SEQRES {:>3} {} {:>4}  {}",
+                    serial,
+                    s.chain_id.unwrap_or(' '),
+                    s.num_res,
+                    residues
+                        .iter()
+                        .map(|r| format!("{:>3}", r))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                )
+            })
+            .collect(),
+        Record::Het(h) => vec![format!(
+            "HET    {:>3}  {}{:>4}{}  {:>5}     {}",
+            h.het_id,
+            h.chain_id,
+            h.residue_seq,
+            h.insertion_code.unwrap_or(' '),
+            h.num_het_atoms,
+            optional(&h.text)
+        )
+        .trim_end()
+        .to_owned()],
+        Record::HetName(h) => hetnam_lines(h),
+        Record::Formul(f) => formul_lines(f),
+        Record::Helix(h) => vec![helix_line(h)],
+        Record::Sheet(s) => vec![sheet_line(s)],
+        Record::SsBond(s) => vec![ssbond_line(s)],
+        Record::CisPep(c) => vec![cispep_line(c)],
+        Record::Site(s) => site_lines(s),
+        Record::Cryst1(c) => vec![format!(
+            "CRYST1{:>9.3}{:>9.3}{:>9.3}{:>7.2}{:>7.2}{:>7.2} {:<11}{:>4}",
+            c.a, c.b, c.c, c.alpha, c.beta, c.gamma, c.space_group, c.z
+        )],
+        Record::Origx(o) => transformation_lines("ORIGX", &o.matrix, &o.translation, None),
+        Record::Scale(s) => transformation_lines("SCALE", &s.matrix, &s.translation, None),
+        Record::Mtrix(m) => transformation_lines(
+            "MTRIX",
+            &m.matrix,
+            &m.translation,
+            Some((m.serial, m.given)),
+        ),
+        Record::Atom(a) => atom_lines(a),
+        Record::Ter(t) => vec![t
+            .serial
+            .map_or_else(|| "TER".to_owned(), |s| format!("TER   {:>5}", s))],
+        Record::Model(m) => std::iter::once(format!("MODEL     {:>4}", m.serial))
+            .chain(m.atoms.iter().flat_map(atom_lines))
+            .chain(std::iter::once("ENDMDL".to_owned()))
+            .collect(),
+        Record::Conect(c) => vec![std::iter::once(format!("CONECT{:>5}", c.serial))
+            .chain(c.bonded.iter().map(|b| format!("{:>5}", b)))
+            .collect()],
+        Record::Master(m) => vec![format!(
+            "MASTER    {:>5}{:>5}{:>5}{:>5}{:>5}{:>5}{:>5}{:>5}{:>5}{:>5}{:>5}{:>5}",
+            m.num_remark,
+            0,
+            m.num_het,
+            m.num_helix,
+            m.num_sheet,
+            0,
+            m.num_site,
+            m.num_xform,
+            m.num_coord,
+            m.num_ter,
+            m.num_conect,
+            m.num_seq
+        )],
+        Record::FootNote(f) => ftnote_lines(f),
+    }
+}
+
+/// Writes the records of `pdb` in the order of the sections of the format, followed by an
+/// END record. Records keep their relative order within a section, footnotes are written
+/// last. Every record is written, although lines which were not read into a record, such
+/// as the free text of REMARK 2, 3 and 465 or unsupported record types, are not
+/// reproduced. Parsing the written text gives back the records of `pdb`.
+pub fn to_pdb_string(pdb: &PdbFile<Vec<Record>>) -> String {
+    let mut records = pdb.records().iter().collect::<Vec<_>>();
+    records.sort_by_key(|r| record_section(r).map_or(u8::MAX, |(rank, _)| rank));
+    records
+        .into_iter()
+        .flat_map(record_lines)
+        .chain(std::iter::once("END".to_owned()))
+        .map(|line| line + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let input = r#"HEADER    HYDROLASE                               20-APR-99   1CJY
+OBSLTE     31-JAN-94 1MBP      2MBP
+TITLE     HUMAN CYTOSOLIC PHOSPHOLIPASE A2 WITH A SUFFICIENTLY LONG TITLE TO
+TITLE    2 WRAP ONTO A SECOND LINE
+SPLIT      1VOQ 1VOR 1VOS
+CAVEAT     1CJY    INCORRECT CHIRALITY AT RESIDUE A 23
+COMPND    MOL_ID: 1;
+COMPND   2 MOLECULE: CYTOSOLIC PHOSPHOLIPASE A2;
+COMPND   3 CHAIN: A, B
+SOURCE    MOL_ID: 1;
+SOURCE   2 ORGANISM_SCIENTIFIC: HOMO SAPIENS;
+SOURCE   3 ORGANISM_TAXID: 9606
+KEYWDS    HYDROLASE, PHOSPHOLIPASE
+EXPDTA    X-RAY DIFFRACTION
+NUMMDL    2
+MDLTYP    CA ATOMS ONLY, CHAIN A; MINIMIZED AVERAGE
+AUTHOR    A.DESSEN,J.TANG,H.SCHMIDT,M.STAHL,J.D.CLARK,J.SEEHRA,W.S.SOMERS,
+AUTHOR   2 A.N.OTHER
+REVDAT   2   24-FEB-09 1CJY    1       VERSN
+REVDAT   1   26-SEP-01 1CJY    0       HEADER COMPND REMARK JRNL
+REVDAT   1 2                           ATOM
+SPRSDE     17-JUL-84 1CJY      1ABC
+JRNL        AUTH   A.DESSEN,J.TANG,H.SCHMIDT,M.STAHL,J.D.CLARK,J.SEEHRA,
+JRNL        AUTH 2 W.S.SOMERS
+JRNL        TITL   STRUCTURE OF HUMAN CYTOSOLIC PHOSPHOLIPASE A2 AT 2.5
+JRNL        TITL 2 ANGSTROMS RESOLUTION
+JRNL        EDIT   J.SMITH,K.JONES
+JRNL        REF    CELL(CAMBRIDGE,MASS.)         V.  97   349 1999
+JRNL        PUBL   CELL PRESS, CAMBRIDGE
+JRNL        REFN                   ISSN 0092-8674
+JRNL        PMID   10319815
+JRNL        DOI    10.1016/S0092-8674(00)80744-8
+REMARK   2
+REMARK   2 RESOLUTION.    2.50 ANGSTROMS.
+REMARK   3 REFINEMENT.
+REMARK   3   R VALUE            (WORKING SET) : 0.225
+REMARK   3   FREE R VALUE                     : 0.281
+REMARK   4 1CJY COMPLIES WITH FORMAT V. 3.30, 13-JUL-11
+REMARK 350 BIOMOLECULE: 1
+REMARK 350 APPLY THE FOLLOWING TO CHAINS: A, B
+REMARK 350   BIOMT1   1  1.000000  0.000000  0.000000        0.00000
+REMARK 350   BIOMT2   1  0.000000  1.000000  0.000000        0.00000
+REMARK 350   BIOMT3   1  0.000000  0.000000  1.000000        0.00000
+REMARK 350 APPLY THE FOLLOWING TO CHAINS: C
+REMARK 350   BIOMT1   2 -1.000000  0.000000  0.000000       52.00000
+REMARK 350   BIOMT2   2  0.000000  1.000000  0.000000        0.00000
+REMARK 350   BIOMT3   2  0.000000  0.000000 -1.000000       30.95000
+REMARK 465 MISSING RESIDUES
+REMARK 465   M RES C SSSEQI
+REMARK 465     GLY A    -2
+REMARK 465     SER A    -1A
+REMARK 800 SITE_IDENTIFIER: AC1
+REMARK 800 EVIDENCE_CODE: SOFTWARE
+REMARK 800 SITE_DESCRIPTION: BINDING SITE FOR RESIDUE ZN A 301
+DBREF  1CJY A    1   749  UNP    P47712   PA24A_HUMAN      1    749             
+DBREF1 1CJY B    1   749  UNIMES               UPI000148A153                    
+DBREF2 1CJY B     MES00005880000                      1         749             
+SEQADV 1CJY ALA A   52  UNP  P47712    THR    52 ENGINEERED MUTATION
+SEQRES   1 A   14  MET SER PHE ILE ASP PRO TYR GLN HIS ILE ILE VAL GLU
+SEQRES   2 A   14  HIS
+HET     ZN  A 301       1
+HETNAM      ZN ZINC ION
+FORMUL   3   ZN    ZN 2+
+FORMUL   4  HOH   *52(H2 O)
+HELIX    1   1 SER A    2  HIS A   14  1                                  13
+SHEET    1   A 2 ILE A   4  VAL A  12  0
+SHEET    2   A 2 TYR A   7  GLN A   8 -1
+SSBOND   1 CYS A    3    CYS A   12                          1555   1555  2.03
+CISPEP   1 PRO A    6    TYR A    7          0        -5.87
+SITE     1 AC1  2 HIS A   1  HIS A  14
+CRYST1   52.000   58.600   61.900  90.00  90.00  90.00 P 21 21 21    8
+ORIGX1      1.000000  0.000000  0.000000        0.00000
+ORIGX2      0.000000  1.000000  0.000000        0.00000
+ORIGX3      0.000000  0.000000  1.000000        0.00000
+SCALE1      0.019231  0.000000  0.000000        0.00000
+SCALE2      0.000000  0.017065  0.000000        0.00000
+SCALE3      0.000000  0.000000  0.016155        0.00000
+MTRIX1   1 -1.000000  0.000000  0.000000       52.00000    1
+MTRIX2   1  0.000000 -1.000000  0.000000       29.30000    1
+MTRIX3   1  0.000000  0.000000  1.000000        0.00000    1
+ATOM      1  N   MET A   1      47.699  22.521  30.384  1.00 37.82           N
+ANISOU    1  N   MET A   1     4374   4620   5377    -90   -236    132       N
+ATOM      2  CA  MET A   1      48.661  22.866  29.347  1.00 27.31           C
+TER       3      MET A   1
+HETATM    4 ZN    ZN A 301      17.120  36.051  12.580  1.00 20.14          ZN
+CONECT    4    2
+MASTER        7    0    1    1    2    0    1    9    3    1    1    2
+FTNOTE   1 RESIDUE PRO A 6 IS A CIS PROLINE
+END
+"#;
+        let (rest, pdb) = crate::parse(input).unwrap();
+        assert_eq!(rest, b"END\n");
+        let written = to_pdb_string(&pdb);
+        let (rest, reparsed) = crate::parse(&written).unwrap();
+        assert_eq!(rest, b"END\n");
+        assert_eq!(reparsed.records(), pdb.records());
+        assert_eq!(pdb.records().len(), 54);
+        assert_eq!(
+            pdb.records()
+                .iter()
+                .filter(|r| matches!(r, Record::Remark(_)))
+                .count(),
+            1
+        );
+        assert!(written.ends_with("FTNOTE   1 RESIDUE PRO A 6 IS A CIS PROLINE\nEND\n"));
+        assert_eq!(to_pdb_string(&reparsed), written);
+    }
+}